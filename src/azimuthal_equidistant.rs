@@ -0,0 +1,143 @@
+use crate::geomath;
+use crate::problem::direct::{direct_problem, ArcDist, AzimuthOnly, Azi, DirectProblemReturnValue, DistanceIn, LatLon, M12};
+use crate::{Geodesic, InverseGeodesic};
+
+/// The azimuthal equidistant projection.
+///
+/// The projection is centered on (`lat0`, `lon0`). A point at distance `s12`
+/// along the geodesic from the center with azimuth `azi0` is mapped to
+/// `x = s12 * sin(azi0)`, `y = s12 * cos(azi0)`, so distances and azimuths
+/// from the center are preserved. Unlike most projections this covers the
+/// whole ellipsoid, although the point antipodal to the center is spread
+/// over a curve. This is the projection for range rings around a site.
+#[derive(Debug, Clone)]
+pub struct AzimuthalEquidistant<'a> {
+    geod: &'a Geodesic,
+    eps: f64,
+}
+
+/// AzimuthalEquidistant maps between geographic coordinates and the
+/// azimuthal equidistant projection centered on a given point.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{AzimuthalEquidistant, Geodesic};
+///
+/// let g = Geodesic::wgs84();
+/// let proj = AzimuthalEquidistant::new(g);
+///
+/// // Singapore Changi Airport in the projection centered on JFK
+/// let (x, y, _azi, _rk) = proj.forward(40.64, -73.78, 1.36, 103.99);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x, 885008.59035316797, epsilon = 1e-6);
+/// assert_relative_eq!(y, 15321974.848374253, epsilon = 1e-6);
+///
+/// let (lat, lon, _azi, _rk) = proj.reverse(40.64, -73.78, x, y);
+/// assert_relative_eq!(lat, 1.36, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 103.99, epsilon = 1e-12);
+/// ```
+impl<'a> AzimuthalEquidistant<'a> {
+    /// Create a new AzimuthalEquidistant projection using a Geodesic.
+    pub fn new(geod: &'a Geodesic) -> Self {
+        AzimuthalEquidistant {
+            geod,
+            eps: 0.01 * f64::MIN_POSITIVE.sqrt(),
+        }
+    }
+
+    /// Project (`lat`, `lon`) onto the plane centered on (`lat0`, `lon0`).
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - azi azimuth of the geodesic through the point (degrees).
+    /// - rk reciprocal of the azimuthal scale at the point, `m12 / s12`.
+    pub fn forward(&self, lat0: f64, lon0: f64, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let (s12, azi0, azi, m12, a12): (f64, f64, f64, f64, f64) = self.geod.inverse(lat0, lon0, lat, lon);
+        let (x, y) = geomath::sincosd(azi0);
+        (x * s12, y * s12, azi, self.rk(a12, m12, s12))
+    }
+
+    /// Find the point which (`x`, `y`) in the projection centered on
+    /// (`lat0`, `lon0`) came from.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees).
+    /// - azi azimuth of the geodesic through the point (degrees).
+    /// - rk reciprocal of the azimuthal scale at the point, `m12 / s12`.
+    pub fn reverse(&self, lat0: f64, lon0: f64, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let azi0 = geomath::atan2d(x, y);
+        let s12 = x.hypot(y);
+        let out: ArcDist<M12<Azi<LatLon>>> =
+            direct_problem(self.geod, lat0, lon0, AzimuthOnly::from(azi0), DistanceIn::from(s12));
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            self.rk(out.get_a12(), out.get_m12(), s12),
+        )
+    }
+
+    // m12 / s12, which tends to 1 at the center
+    fn rk(&self, a12: f64, m12: f64, s12: f64) -> f64 {
+        if a12 <= self.eps {
+            1.0
+        } else {
+            m12 / s12
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DirectGeodesic;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        let geod = Geodesic::wgs84();
+        let proj = AzimuthalEquidistant::new(geod);
+
+        let (x, y, azi, rk) = proj.forward(40.64, -73.78, 1.36, 103.99);
+        assert_relative_eq!(x, 885008.59035316797, epsilon = 1e-6);
+        assert_relative_eq!(y, 15321974.848374253, epsilon = 1e-6);
+        assert_relative_eq!(azi, 177.48784020815515, epsilon = 1e-12);
+        assert_relative_eq!(rk, 0.28034140881899067, epsilon = 1e-14);
+
+        let (lat, lon, azi2, rk2) = proj.reverse(40.64, -73.78, x, y);
+        assert_relative_eq!(lat, 1.36, epsilon = 1e-12);
+        assert_relative_eq!(lon, 103.99, epsilon = 1e-12);
+        assert_relative_eq!(azi2, azi, epsilon = 1e-9);
+        assert_relative_eq!(rk2, rk, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_center() {
+        let geod = Geodesic::wgs84();
+        let proj = AzimuthalEquidistant::new(geod);
+
+        let (x, y, _azi, rk) = proj.forward(40.64, -73.78, 40.64, -73.78);
+        assert_eq!((x, y, rk), (0.0, 0.0, 1.0));
+        let (lat, lon, _azi, rk) = proj.reverse(40.64, -73.78, 0.0, 0.0);
+        assert_relative_eq!(lat, 40.64, epsilon = 1e-12);
+        assert_relative_eq!(lon, -73.78, epsilon = 1e-12);
+        assert_eq!(rk, 1.0);
+    }
+
+    #[test]
+    fn test_range_ring() {
+        // Points 1000 km from the center lie on a circle of radius 1000 km
+        let geod = Geodesic::wgs84();
+        let proj = AzimuthalEquidistant::new(geod);
+        for i in 0..12 {
+            let azi1 = f64::from(i) * 30.0;
+            let (lat, lon): (f64, f64) = geod.direct(-33.95, 151.18, azi1, 1e6);
+            let (x, y, _azi, _rk) = proj.forward(-33.95, 151.18, lat, lon);
+            assert_relative_eq!(x.hypot(y), 1e6, epsilon = 1e-6);
+            assert_relative_eq!(geomath::atan2d(x, y), geomath::ang_normalize(azi1), epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::geodesic_capability as caps;
+use crate::geodesic_line::GeodesicLine;
+use crate::geomath;
+use crate::traits::caps::{Azimuth, Distance, Empty};
+use crate::{DirectGeodesic, Geodesic};
+
+/// The Cassini-Soldner projection.
+///
+/// The central meridian through (`lat0`, `lon0`) is mapped to the `y` axis,
+/// with `y` the distance along it from the origin. A point is mapped using
+/// the geodesic through it which crosses the central meridian at right
+/// angles: `y` is where it crosses, and `x` is the distance along it from the
+/// central meridian. The projection is only useful within a few degrees of
+/// the central meridian.
+///
+/// This is implemented with a [`GeodesicLine`] along the central meridian
+/// and the inverse problem, giving results accurate to round-off for any
+/// point (unlike the series usually used).
+#[derive(Debug, Clone)]
+pub struct CassiniSoldner<'a> {
+    geod: &'a Geodesic,
+    meridian: GeodesicLine<'a>,
+    sbet0: f64,
+    cbet0: f64,
+}
+
+/// CassiniSoldner maps between geographic coordinates and the Cassini-Soldner
+/// projection with a given origin.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{CassiniSoldner, Geodesic};
+///
+/// let g = Geodesic::wgs84();
+/// // Centered on Paris
+/// let proj = CassiniSoldner::new(g, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+///
+/// // Calais
+/// let (x, y, _azi, _rk) = proj.forward(50.9, 1.8);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x, -37518.854545013535, epsilon = 1e-6);
+/// assert_relative_eq!(y, 230003.56182826086, epsilon = 1e-6);
+///
+/// let (lat, lon, _azi, _rk) = proj.reverse(x, y);
+/// assert_relative_eq!(lat, 50.9, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 1.8, epsilon = 1e-12);
+/// ```
+impl<'a> CassiniSoldner<'a> {
+    /// Create a new CassiniSoldner projection using a Geodesic, with its
+    /// origin at (`lat0`, `lon0`).
+    pub fn new(geod: &'a Geodesic, lat0: f64, lon0: f64) -> Self {
+        let (meridian, sbet0, cbet0) = Self::origin(geod, lat0, lon0);
+        CassiniSoldner {
+            geod,
+            meridian,
+            sbet0,
+            cbet0,
+        }
+    }
+
+    /// Move the origin of the projection to (`lat0`, `lon0`).
+    pub fn reset(&mut self, lat0: f64, lon0: f64) {
+        let (meridian, sbet0, cbet0) = Self::origin(self.geod, lat0, lon0);
+        self.meridian = meridian;
+        self.sbet0 = sbet0;
+        self.cbet0 = cbet0;
+    }
+
+    fn origin(geod: &'a Geodesic, lat0: f64, lon0: f64) -> (GeodesicLine<'a>, f64, f64) {
+        let meridian = geod.line(
+            lat0,
+            lon0,
+            0.0,
+            caps::LATITUDE | caps::LONGITUDE | caps::DISTANCE | caps::DISTANCE_IN | caps::AZIMUTH,
+        );
+        // The reduced latitude of the origin
+        let (mut sbet0, mut cbet0) = geomath::sincosd(meridian.latitude());
+        sbet0 *= 1.0 - geod.flattening();
+        geomath::norm(&mut sbet0, &mut cbet0);
+        (meridian, sbet0, cbet0)
+    }
+
+    /// Latitude of the origin (degrees).
+    pub fn latitude_origin(&self) -> f64 {
+        self.meridian.latitude()
+    }
+
+    /// Longitude of the origin (degrees).
+    pub fn longitude_origin(&self) -> f64 {
+        self.meridian.longitude()
+    }
+
+    /// Project (`lat`, `lon`).
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - azi azimuth of the easting (x) direction at the point (degrees).
+    /// - rk reciprocal of the azimuthal northing scale at the point.
+    pub fn forward(&self, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let (dlon, _) = geomath::ang_diff(self.longitude_origin(), lon);
+        // The geodesic at right angles to the central meridian is symmetric
+        // about it, so find it by solving the inverse problem across it
+        let (sig12, s12, salp1, calp1, salp2, calp2, _, _, _, _) =
+            self.geod
+                ._gen_inverse::<Azimuth<Distance<Empty>>>(lat, -dlon.abs(), lat, dlon.abs());
+        let mut sig12 = sig12 * 0.5;
+        let mut s12 = s12 * 0.5;
+        let mut azi1 = geomath::atan2d(salp1, calp1);
+        let mut azi2 = geomath::atan2d(salp2, calp2);
+        if s12 == 0.0 {
+            let da = geomath::ang_diff(azi1, azi2).0 / 2.0;
+            if dlon.abs() <= 90.0 {
+                azi1 = 90.0 - da;
+                azi2 = 90.0 + da;
+            } else {
+                azi1 = -90.0 - da;
+                azi2 = -90.0 + da;
+            }
+        }
+        if dlon.is_sign_negative() {
+            azi2 = azi1;
+            s12 = -s12;
+            sig12 = -sig12;
+        }
+        let x = s12;
+        let azi = geomath::ang_normalize(azi2);
+        let perp = self.geod.line(lat, dlon, azi, caps::GEODESICSCALE);
+        #[allow(non_snake_case)]
+        let (_, _, _, _, _, _, _, M21, _) = perp._gen_position(true, -sig12, caps::GEODESICSCALE);
+        let rk = M21;
+
+        // Where the perpendicular crosses the central meridian
+        let (salp0, calp0) = geomath::sincosd(perp.equatorial_azimuth());
+        let sbet1 = if lat >= 0.0 { calp0 } else { -calp0 };
+        let cbet1 = if dlon.abs() <= 90.0 { salp0.abs() } else { -salp0.abs() };
+        let sbet01 = sbet1 * self.cbet0 - cbet1 * self.sbet0;
+        let cbet01 = cbet1 * self.cbet0 + sbet1 * self.sbet0;
+        let sig01 = geomath::atan2d(sbet01, cbet01);
+        let (_, _, _, _, y, _, _, _, _) = self.meridian._gen_position(true, sig01, caps::DISTANCE);
+        (x, y, azi, rk)
+    }
+
+    /// Find the point which (`x`, `y`) came from.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees).
+    /// - azi azimuth of the easting (x) direction at the point (degrees).
+    /// - rk reciprocal of the azimuthal northing scale at the point.
+    pub fn reverse(&self, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let (_, lat1, lon1, azi0, _, _, _, _, _) =
+            self.meridian
+                ._gen_position(false, y, caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH);
+        #[allow(non_snake_case)]
+        let (lat, lon, azi, M12, _M21): (f64, f64, f64, f64, f64) = self.geod.direct(lat1, lon1, azi0 + 90.0, x);
+        (lat, lon, azi, M12)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InverseGeodesic;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        let geod = Geodesic::wgs84();
+        let proj = CassiniSoldner::new(geod, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+
+        let (x, y, azi, rk) = proj.forward(50.9, 1.8);
+        assert_relative_eq!(x, -37518.854545013535, epsilon = 1e-6);
+        assert_relative_eq!(y, 230003.56182826086, epsilon = 1e-6);
+        assert_relative_eq!(azi, 89.586103815364325, epsilon = 1e-12);
+        assert_relative_eq!(rk, 0.999982722207021, epsilon = 1e-14);
+
+        let (lat, lon, azi, rk) = proj.reverse(-38e3, 230e3);
+        assert_relative_eq!(lat, 50.899936540417492, epsilon = 1e-12);
+        assert_relative_eq!(lon, 1.7931610942128962, epsilon = 1e-12);
+        assert_relative_eq!(azi, 89.58079669865019, epsilon = 1e-12);
+        assert_relative_eq!(rk, 0.99998227622251523, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_far_from_meridian() {
+        // Exact everywhere, not just near the central meridian
+        let geod = Geodesic::wgs84();
+        let proj = CassiniSoldner::new(geod, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+
+        let (x, y, azi, rk) = proj.forward(-30.0, 100.0);
+        assert_relative_eq!(x, 6584631.4299048018, epsilon = 1e-6);
+        assert_relative_eq!(y, -16857964.042736541, epsilon = 1e-6);
+        assert_relative_eq!(azi, -15.019742292756211, epsilon = 1e-12);
+        assert_relative_eq!(rk, 0.51464411754149419, epsilon = 1e-14);
+
+        let (lat, lon, _, _) = proj.reverse(x, y);
+        assert_relative_eq!(lat, -30.0, epsilon = 1e-12);
+        assert_relative_eq!(lon, 100.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_reset() {
+        let geod = Geodesic::wgs84();
+        let mut proj = CassiniSoldner::new(geod, 0.0, 0.0);
+        let (x, y, azi, rk) = proj.forward(0.0, 0.0);
+        assert_eq!((x, y), (0.0, 0.0));
+        assert_relative_eq!(azi, 90.0);
+        assert_relative_eq!(rk, 1.0);
+
+        proj.reset(48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+        assert_relative_eq!(proj.latitude_origin(), 48.0 + 50.0 / 60.0);
+        assert_relative_eq!(proj.longitude_origin(), 2.0 + 20.0 / 60.0);
+        let (x, y, _, _) = proj.forward(50.9, 1.8);
+        assert_relative_eq!(x, -37518.854545013535, epsilon = 1e-6);
+        assert_relative_eq!(y, 230003.56182826086, epsilon = 1e-6);
+
+        // Points on the central meridian
+        let (x, y, _, _) = proj.forward(60.0, 2.0 + 20.0 / 60.0);
+        assert_eq!(x, 0.0);
+        let s12: f64 = geod.inverse(48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0, 60.0, 2.0 + 20.0 / 60.0);
+        assert_relative_eq!(y, s12, epsilon = 1e-6);
+    }
+}
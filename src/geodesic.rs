@@ -4,9 +4,12 @@
 use crate::geodesic_capability as caps;
 use crate::geodesic_line;
 use crate::geomath;
-use crate::traits::{Caps,Distance,Empty,ReducedLength,GeodesicScale,Area,Azimuth,CheckN};
+use crate::problem::direct::{direct_problem, DirectProblemReturnValue, AzimuthOnly, ArcIn, DistanceIn};
+use crate::problem::direct::{ArcDist, Area as RetArea, Azi, Dist, LatLon, M12, M12M21};
+use crate::problem::inverse::{self, inverse_problem, InverseProblemReturnValue};
+use crate::traits::{Caps,Distance,Empty,ReducedLength,CheckN};
 use crate::traits::{LengthsReturnValue,M0,S12b,M21M12,M12b};
-#[cfg(test)]use crate::traits::{Standard,All};
+#[cfg(test)]use crate::traits::{Standard,All,GeodesicScale,Area};
 use crate::cached_weights::{Weights,C1Coeff,C2Coeff};
 use crate::internals::constants::{TOL0,TOL1,TOL2,TINY,TOL_B,X_THRESH,ITERATIONS,MAX_ITERATIONS,WGS84_A,WGS84_F};
 use std::sync;
@@ -20,7 +23,7 @@ pub struct Geodesic {
     pub(in crate) _f1: f64,
     pub(in crate) _e2: f64,
     pub(in crate) _ep2: f64,
-    _n: f64,
+    pub(in crate) _n: f64,
     pub(in crate) _b: f64,
     pub(in crate) _c2: f64,
     _etol2: f64,
@@ -733,12 +736,274 @@ impl Geodesic {
         line._gen_position(arcmode, s12_a12, outmask)
     }
 
+    /// Create a [`GeodesicLine`](geodesic_line::GeodesicLine) starting at (lat1, lon1) with azimuth azi1.
+    ///
+    /// `caps` is a bitor'ed combination of [`capability`](crate::capability) values
+    /// specifying which quantities the line will be able to compute. Include
+    /// `DISTANCE_IN` to allow positions to be found by distance.
+    pub fn line(&self, lat1: f64, lon1: f64, azi1: f64, caps: u64) -> geodesic_line::GeodesicLine<'_> {
+        geodesic_line::GeodesicLine::new(self, lat1, lon1, azi1, Some(caps), None, None)
+    }
+
+    /// Create a [`GeodesicLine`](geodesic_line::GeodesicLine) starting at (lat1, lon1) with
+    /// azimuth azi1, with its reference point 3 the distance s12 (meters) along it.
+    ///
+    /// `caps` is as for [`Geodesic::line`], with `DISTANCE_IN` always added.
+    pub fn direct_line(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64, caps: u64) -> geodesic_line::GeodesicLine<'_> {
+        self._gen_direct_line(lat1, lon1, azi1, false, s12, caps)
+    }
+
+    /// Create a [`GeodesicLine`](geodesic_line::GeodesicLine) starting at (lat1, lon1) with
+    /// azimuth azi1, with its reference point 3 the arc length a12 (degrees) along it.
+    ///
+    /// `caps` is as for [`Geodesic::line`]. Include `DISTANCE` to have the distance
+    /// to point 3 computed.
+    pub fn arc_direct_line(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64, caps: u64) -> geodesic_line::GeodesicLine<'_> {
+        self._gen_direct_line(lat1, lon1, azi1, true, a12, caps)
+    }
+
+    /// The general form of [`Geodesic::direct_line`] and [`Geodesic::arc_direct_line`].
+    pub fn _gen_direct_line(
+        &self,
+        lat1: f64,
+        lon1: f64,
+        azi1: f64,
+        arcmode: bool,
+        s12_a12: f64,
+        mut caps: u64,
+    ) -> geodesic_line::GeodesicLine<'_> {
+        if !arcmode {
+            caps |= caps::DISTANCE_IN;
+        }
+        let mut line = geodesic_line::GeodesicLine::new(self, lat1, lon1, azi1, Some(caps), None, None);
+        line._gen_set_distance(arcmode, s12_a12);
+        line
+    }
+
+    /// Create a [`GeodesicLine`](geodesic_line::GeodesicLine) from the solution of the
+    /// inverse problem between (lat1, lon1) and (lat2, lon2).
+    ///
+    /// The second point becomes the line's reference point 3, so its distance and
+    /// arc length are available from [`GeodesicLine::distance`](geodesic_line::GeodesicLine::distance)
+    /// and [`GeodesicLine::arc`](geodesic_line::GeodesicLine::arc). `caps` is as for
+    /// [`Geodesic::line`].
+    ///
+    /// ```rust
+    /// // Find the midpoint of the geodesic between JFK and Singapore Changi.
+    /// use geographiclib_rs::{capability as caps, Geodesic, LinePosition};
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let line = g.inverse_line(40.64, -73.78, 1.36, 103.99, caps::STANDARD | caps::DISTANCE_IN);
+    /// let (lat, lon) = line.position(line.distance() / 2.0);
+    ///
+    /// use approx::assert_relative_eq;
+    /// assert_relative_eq!(line.distance(), 15347512.94051294);
+    /// assert_relative_eq!(lat, 70.34198863284013, epsilon = 1e-13);
+    /// assert_relative_eq!(lon, 97.03061194686967, epsilon = 1e-13);
+    /// ```
+    pub fn inverse_line(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64, caps: u64) -> geodesic_line::GeodesicLine<'_> {
+        let (a12, s12, salp1, calp1, _salp2, _calp2, _m12, _M12, _M21, _S12) =
+            self._gen_inverse::<Distance<Empty>>(lat1, lon1, lat2, lon2);
+        let azi1 = geomath::atan2d(salp1, calp1);
+        geodesic_line::GeodesicLine::new(self, lat1, lon1, azi1, Some(caps), Some(salp1), Some(calp1))
+            .with_end_point(s12, a12)
+    }
+
+    /// Iterate over `n + 1` evenly spaced points on the geodesic from (lat1, lon1) to
+    /// (lat2, lon2), yielding (lat, lon, azi) in degrees.
+    ///
+    /// The geodesic is split into `n` segments of equal length (`n` is taken to
+    /// be at least 1). The first point is (lat1, lon1) and the last is exactly
+    /// (lat2, lon2), with the longitude reduced to [-180, 180].
+    ///
+    /// ```rust
+    /// // Waypoints every 1/10th of the way between JFK and Singapore Changi.
+    /// use geographiclib_rs::Geodesic;
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let points: Vec<(f64, f64, f64)> = g.waypoints(40.64, -73.78, 1.36, 103.99, 10).collect();
+    ///
+    /// use approx::assert_relative_eq;
+    /// assert_eq!(points.len(), 11);
+    /// let (lat, lon, _azi) = points[5];
+    /// assert_relative_eq!(lat, 70.34198863284013, epsilon = 1e-13);
+    /// assert_relative_eq!(lon, 97.03061194686967, epsilon = 1e-13);
+    /// assert_eq!(points[10].0, 1.36);
+    /// assert_eq!(points[10].1, 103.99);
+    /// ```
+    pub fn waypoints(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64, n: usize) -> geodesic_line::Waypoints<'_> {
+        let caps = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::DISTANCE_IN;
+        self.inverse_line(lat1, lon1, lat2, lon2, caps)
+            .waypoints(n)
+            .with_end_point(geomath::lat_fix(lat2), geomath::ang_normalize(lon2))
+    }
+
+    /// Solve the direct problem, returning the outputs selected by `C` as a [`DirectResult`].
+    ///
+    /// `C` is a set of type-level [`caps`](crate::traits::caps), e.g.
+    /// `Latitude<Longitude<Empty>>`. Fields of the result for capabilities not in
+    /// `C` are `None`. The last argument is the distance s12 (meters), or the arc
+    /// length a12 (degrees) if `C` includes [`ArcMode`](crate::traits::caps::ArcMode).
+    /// If `C` includes [`LongUnroll`](crate::traits::caps::LongUnroll), lon2 is
+    /// unrolled rather than reduced to [-180°, 180°].
+    ///
+    /// ```rust
+    /// // Example, determine the point 10000 km NE of JFK:
+    /// use geographiclib_rs::Geodesic;
+    /// use geographiclib_rs::traits::caps::{Empty, GeodesicScale, Latitude, Longitude};
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let r = g.direct_result::<Latitude<Longitude<GeodesicScale<Empty>>>>(40.64, -73.78, 45.0, 10e6);
+    ///
+    /// use approx::assert_relative_eq;
+    /// assert_relative_eq!(r.lat2.unwrap(), 32.621100463725796);
+    /// assert_relative_eq!(r.lon2.unwrap(), 49.052487092959836);
+    /// assert_relative_eq!(r.M12.unwrap(), 0.0041210569938536093, epsilon = 1e-13);
+    /// assert_relative_eq!(r.M21.unwrap(), 0.0034199538880231237, epsilon = 1e-13);
+    /// assert_eq!(r.azi2, None);
+    /// assert_eq!(r.m12, None);
+    /// ```
+    pub fn direct_result<C: Caps>(&self, lat1: f64, lon1: f64, azi1: f64, s12_a12: f64) -> DirectResult {
+        let (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) =
+            self._gen_direct(lat1, lon1, azi1, C::ARC_MODE, s12_a12, C::MASK);
+        DirectResult {
+            lat2: if C::LATITUDE { Some(lat2) } else { None },
+            lon2: if C::LONGITUDE { Some(lon2) } else { None },
+            azi2: if C::AZIMUTH { Some(azi2) } else { None },
+            s12: if !C::ARC_MODE {
+                Some(s12_a12)
+            } else if C::DISTANCE {
+                Some(s12)
+            } else {
+                None
+            },
+            a12,
+            m12: if C::REDUCEDLENGTH { Some(m12) } else { None },
+            M12: if C::GEODESICSCALE { Some(M12) } else { None },
+            M21: if C::GEODESICSCALE { Some(M21) } else { None },
+            S12: if C::AREA { Some(S12) } else { None },
+        }
+    }
+
+    /// Solve the inverse problem, returning the outputs selected by `C` as an [`InverseResult`].
+    ///
+    /// `C` is a set of type-level [`caps`](crate::traits::caps), e.g.
+    /// `Distance<Azimuth<Empty>>`. Fields of the result for capabilities not in
+    /// `C` are `None`. `Longitude` returns lon1 and lon2, which are unrolled
+    /// (lon2 - lon1 is the longitude travelled) if `C` includes
+    /// [`LongUnroll`](crate::traits::caps::LongUnroll). `Latitude` and
+    /// `DistanceIn` have no effect.
+    ///
+    /// ```rust
+    /// // Example, determine the distance between JFK and Singapore Changi Airport:
+    /// use geographiclib_rs::Geodesic;
+    /// use geographiclib_rs::traits::caps::{Azimuth, Distance, Empty};
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let r = g.inverse_result::<Distance<Azimuth<Empty>>>(40.64, -73.78, 1.36, 103.99);
+    ///
+    /// use approx::assert_relative_eq;
+    /// assert_relative_eq!(r.s12.unwrap(), 15347512.94051294);
+    /// assert_relative_eq!(r.azi1.unwrap(), 3.3057734780176125, epsilon = 1e-13);
+    /// assert_relative_eq!(r.azi2.unwrap(), 177.48784020815515, epsilon = 1e-13);
+    /// assert_eq!(r.S12, None);
+    /// ```
+    pub fn inverse_result<C: Caps>(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> InverseResult {
+        let (a12, s12, azi1, azi2, m12, M12, M21, S12) =
+            self._gen_inverse_azi::<C>(lat1, lon1, lat2, lon2);
+        let (lon1, lon2) = geomath::inverse_lons(lon1, lon2, C::LONG_UNROLL);
+        InverseResult {
+            lon1: if C::LONGITUDE { Some(lon1) } else { None },
+            lon2: if C::LONGITUDE { Some(lon2) } else { None },
+            s12: if C::DISTANCE { Some(s12) } else { None },
+            azi1: if C::AZIMUTH { Some(azi1) } else { None },
+            azi2: if C::AZIMUTH { Some(azi2) } else { None },
+            a12,
+            m12: if C::REDUCEDLENGTH { Some(m12) } else { None },
+            M12: if C::GEODESICSCALE { Some(M12) } else { None },
+            M21: if C::GEODESICSCALE { Some(M21) } else { None },
+            S12: if C::AREA { Some(S12) } else { None },
+        }
+    }
+
     /// Get the area of the geodesic in square meters
     pub fn area(&self) -> f64 {
         self._c2 * 4.0 * std::f64::consts::PI
     }
 }
 
+// A Geodesic is serialized as just (a, f); the rest, including the cached
+// weights, is recomputed by Geodesic::new on deserialization.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Geodesic {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.a, self.f).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Geodesic {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (a, f) = <(f64, f64)>::deserialize(deserializer)?;
+        Ok(Geodesic::new(a, f))
+    }
+}
+
+/// The outputs of the direct problem, see [`Geodesic::direct_result`].
+///
+/// Outputs which were not asked for are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectResult {
+    /// Latitude of point 2 (degrees).
+    pub lat2: Option<f64>,
+    /// Longitude of point 2 (degrees).
+    pub lon2: Option<f64>,
+    /// (Forward) azimuth at point 2 (degrees).
+    pub azi2: Option<f64>,
+    /// Distance between point 1 and point 2 (meters). Always present unless the
+    /// problem was given an arc length without asking for `Distance`.
+    pub s12: Option<f64>,
+    /// Arc length between point 1 and point 2 (degrees).
+    pub a12: f64,
+    /// Reduced length of the geodesic (meters).
+    pub m12: Option<f64>,
+    /// Geodesic scale of point 2 relative to point 1 (dimensionless).
+    pub M12: Option<f64>,
+    /// Geodesic scale of point 1 relative to point 2 (dimensionless).
+    pub M21: Option<f64>,
+    /// Area under the geodesic (meters<sup>2</sup>).
+    pub S12: Option<f64>,
+}
+
+/// The outputs of the inverse problem, see [`Geodesic::inverse_result`].
+///
+/// Outputs which were not asked for are `None`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InverseResult {
+    /// Longitude of point 1 (degrees).
+    pub lon1: Option<f64>,
+    /// Longitude of point 2 (degrees).
+    pub lon2: Option<f64>,
+    /// Distance between point 1 and point 2 (meters).
+    pub s12: Option<f64>,
+    /// Azimuth at point 1 (degrees).
+    pub azi1: Option<f64>,
+    /// (Forward) azimuth at point 2 (degrees).
+    pub azi2: Option<f64>,
+    /// Arc length between point 1 and point 2 (degrees).
+    pub a12: f64,
+    /// Reduced length of the geodesic (meters).
+    pub m12: Option<f64>,
+    /// Geodesic scale of point 2 relative to point 1 (dimensionless).
+    pub M12: Option<f64>,
+    /// Geodesic scale of point 1 relative to point 2 (dimensionless).
+    pub M21: Option<f64>,
+    /// Area under the geodesic (meters<sup>2</sup>).
+    pub S12: Option<f64>,
+}
+
 /// Place a second point, given the first point, an azimuth, and a distance.
 ///
 /// # Arguments
@@ -791,11 +1056,9 @@ impl DirectGeodesic<(f64, f64)> for Geodesic {
     ///  - lat2 latitude of point 2 (degrees).
     ///  - lon2 longitude of point 2 (degrees).
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64) {
-        let capabilities = caps::LATITUDE | caps::LONGITUDE;
-        let (_a12, lat2, lon2, _azi2, _s12, _m12, _M12, _M21, _S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: LatLon = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
 
-        (lat2, lon2)
+        (out.get_lat2(), out.get_lon2())
     }
 }
 
@@ -807,11 +1070,9 @@ impl DirectGeodesic<(f64, f64, f64)> for Geodesic {
     ///  - lon2 longitude of point 2 (degrees).
     ///  - azi2 (forward) azimuth at point 2 (degrees).
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64) {
-        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH;
-        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: Azi<LatLon> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
 
-        (lat2, lon2, azi2)
+        (out.get_lat2(), out.get_lon2(), out.get_azi2())
     }
 }
 
@@ -824,11 +1085,9 @@ impl DirectGeodesic<(f64, f64, f64, f64)> for Geodesic {
     ///  - azi2 (forward) azimuth at point 2 (degrees).
     ///  - m12 reduced length of geodesic (meters).
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64, f64) {
-        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::REDUCEDLENGTH;
-        let (_a12, lat2, lon2, azi2, _s12, m12, _M12, _M21, _S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: M12<Azi<LatLon>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
 
-        (lat2, lon2, azi2, m12)
+        (out.get_lat2(), out.get_lon2(), out.get_azi2(), out.get_m12())
     }
 }
 
@@ -842,11 +1101,9 @@ impl DirectGeodesic<(f64, f64, f64, f64, f64)> for Geodesic {
     ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
     ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64, f64, f64) {
-        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::GEODESICSCALE;
-        let (_a12, lat2, lon2, azi2, _s12, _m12, M12, M21, _S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: M12M21<Azi<LatLon>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
 
-        (lat2, lon2, azi2, M12, M21)
+        (out.get_lat2(), out.get_lon2(), out.get_azi2(), out.get_M12(), out.get_M21())
     }
 }
 
@@ -861,15 +1118,17 @@ impl DirectGeodesic<(f64, f64, f64, f64, f64, f64)> for Geodesic {
     ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
     ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
     fn direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64, f64, f64, f64) {
-        let capabilities = caps::LATITUDE
-            | caps::LONGITUDE
-            | caps::AZIMUTH
-            | caps::REDUCEDLENGTH
-            | caps::GEODESICSCALE;
-        let (_a12, lat2, lon2, azi2, _s12, m12, M12, M21, _S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: M12<M12M21<Azi<LatLon>>> =
+            direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
 
-        (lat2, lon2, azi2, m12, M12, M21)
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+        )
     }
 }
 
@@ -892,16 +1151,198 @@ impl DirectGeodesic<(f64, f64, f64, f64, f64, f64, f64, f64)> for Geodesic {
         azi1: f64,
         s12: f64,
     ) -> (f64, f64, f64, f64, f64, f64, f64, f64) {
-        let capabilities = caps::LATITUDE
-            | caps::LONGITUDE
-            | caps::AZIMUTH
-            | caps::REDUCEDLENGTH
-            | caps::GEODESICSCALE
-            | caps::AREA;
-        let (a12, lat2, lon2, azi2, _s12, m12, M12, M21, S12) =
-            self._gen_direct(lat1, lon1, azi1, false, s12, capabilities);
+        let out: ArcDist<RetArea<M12<M12M21<Azi<LatLon>>>>> =
+            direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), DistanceIn::from(s12));
+
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+            out.get_S12(),
+            out.get_a12(),
+        )
+    }
+}
+
+/// Place a second point, given the first point, an azimuth, and an arc length.
+///
+/// This is [`DirectGeodesic`] with the distance given as the arc length on
+/// the auxiliary sphere, as for GeographicLib's `ArcDirect`. The distance s12
+/// is returned alongside the position.
+///
+/// # Arguments
+///   - lat1 - Latitude of 1st point [degrees] [-90.,90.]
+///   - lon1 - Longitude of 1st point [degrees] [-180., 180.]
+///   - azi1 - Azimuth at 1st point [degrees] [-180., 180.]
+///   - a12 - Arc length from 1st to 2nd point [degrees] Value may be negative
+///
+/// # Returns
+///
+/// As with [`DirectGeodesic`], only the outputs you ask for are calculated.
+/// See the following impls which return different subsets of the following outputs:
+///
+///  - lat2 latitude of point 2 (degrees).
+///  - lon2 longitude of point 2 (degrees).
+///  - azi2 (forward) azimuth at point 2 (degrees).
+///  - s12 distance between point 1 and point 2 (meters).
+///  - m12 reduced length of geodesic (meters).
+///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+///  - S12 area under the geodesic (meters<sup>2</sup>).
+///
+/// ```rust
+/// // Example, determine the point a quarter of a great circle NE of JFK:
+/// use geographiclib_rs::{Geodesic, ArcDirectGeodesic};
+///
+/// let g = Geodesic::wgs84();
+/// let (lat, lon, az, s12) = g.arc_direct(40.64, -73.78, 45.0, 90.0);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(lat, 32.589189911080098, epsilon = 1e-13);
+/// assert_relative_eq!(lon, 49.083659335557726, epsilon = 1e-13);
+/// assert_relative_eq!(az, 140.42278292955828, epsilon = 1e-13);
+/// assert_relative_eq!(s12, 10004591.827935437, epsilon = 1e-8);
+/// ```
+pub trait ArcDirectGeodesic<T> {
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> T;
+}
+
+impl ArcDirectGeodesic<(f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64) {
+        let out: LatLon = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
 
-        (lat2, lon2, azi2, m12, M12, M21, S12, a12)
+        (out.get_lat2(), out.get_lon2())
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64) {
+        let out: Azi<LatLon> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (out.get_lat2(), out.get_lon2(), out.get_azi2())
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64, f64) {
+        let out: Dist<Azi<LatLon>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (out.get_lat2(), out.get_lon2(), out.get_azi2(), out.get_s12())
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64, f64, f64) {
+        let out: M12<Dist<Azi<LatLon>>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (out.get_lat2(), out.get_lon2(), out.get_azi2(), out.get_s12(), out.get_m12())
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64, f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64, f64, f64, f64) {
+        let out: M12M21<Dist<Azi<LatLon>>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            out.get_s12(),
+            out.get_M12(),
+            out.get_M21(),
+        )
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64, f64, f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64, f64, f64, f64, f64) {
+        let out: M12<M12M21<Dist<Azi<LatLon>>>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            out.get_s12(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+        )
+    }
+}
+
+impl ArcDirectGeodesic<(f64, f64, f64, f64, f64, f64, f64, f64)> for Geodesic {
+    /// See the documentation for the ArcDirectGeodesic trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    ///  - S12 area under the geodesic (meters<sup>2</sup>).
+    fn arc_direct(&self, lat1: f64, lon1: f64, azi1: f64, a12: f64) -> (f64, f64, f64, f64, f64, f64, f64, f64) {
+        let out: RetArea<M12<M12M21<Dist<Azi<LatLon>>>>> = direct_problem(self, lat1, lon1, AzimuthOnly::from(azi1), ArcIn::from(a12));
+
+        (
+            out.get_lat2(),
+            out.get_lon2(),
+            out.get_azi2(),
+            out.get_s12(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+            out.get_S12(),
+        )
     }
 }
 
@@ -963,10 +1404,9 @@ impl InverseGeodesic<f64> for Geodesic {
     /// # Returns
     /// - s12 distance between point 1 and point 2 (meters).
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
-        let (_a12, s12, _azi1, _azi2, _m12, _M12, _M21, _S12) =
-            self._gen_inverse_azi::<Distance<Empty>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Arc> = inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        s12
+        out.get_s12()
     }
 }
 
@@ -977,10 +1417,9 @@ impl InverseGeodesic<(f64, f64)> for Geodesic {
     /// - s12 distance between point 1 and point 2 (meters).
     /// - a12 arc length between point 1 and point 2 (degrees).
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
-        let (a12, s12, _azi1, _azi2, _m12, _M12, _M21, _S12) =
-            self._gen_inverse_azi::<Distance<Empty>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Arc> = inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, a12)
+        (out.get_s12(), out.get_a12())
     }
 }
 
@@ -992,10 +1431,9 @@ impl InverseGeodesic<(f64, f64, f64)> for Geodesic {
     /// - azi2 (forward) azimuth at point 2 (degrees).
     /// - a12 arc length between point 1 and point 2 (degrees).
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64, f64) {
-        let (a12, _s12, azi1, azi2, _m12, _M12, _M21, _S12) =
-            self._gen_inverse_azi::<Azimuth<Empty>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Alp<inverse::Arc> = inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (azi1, azi2, a12)
+        (out.get_azi1(), out.get_azi2(), out.get_a12())
     }
 }
 
@@ -1008,10 +1446,9 @@ impl InverseGeodesic<(f64, f64, f64, f64)> for Geodesic {
     /// - azi2 (forward) azimuth at point 2 (degrees).
     /// - a12 arc length between point 1 and point 2 (degrees).
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64, f64, f64) {
-        let (a12, s12, azi1, azi2, _m12, _M12, _M21, _S12) =
-            self._gen_inverse_azi::<Distance<Azimuth<Empty>>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Alp<inverse::Arc>> = inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, azi1, azi2, a12)
+        (out.get_s12(), out.get_azi1(), out.get_azi2(), out.get_a12())
     }
 }
 
@@ -1025,10 +1462,10 @@ impl InverseGeodesic<(f64, f64, f64, f64, f64)> for Geodesic {
     /// - m12 reduced length of geodesic (meters).
     /// - a12 arc length between point 1 and point 2 (degrees).
     fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64, f64, f64, f64) {
-        let (a12, s12, azi1, azi2, m12, _M12, _M21, _S12) =
-            self._gen_inverse_azi::<ReducedLength<Distance<Azimuth<Empty>>>>(lat1, lon1, lat2, lon2);
+        let out: inverse::M12<inverse::Dist<inverse::Alp<inverse::Arc>>> =
+            inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, azi1, azi2, m12, a12)
+        (out.get_s12(), out.get_azi1(), out.get_azi2(), out.get_m12(), out.get_a12())
     }
 }
 
@@ -1049,10 +1486,17 @@ impl InverseGeodesic<(f64, f64, f64, f64, f64, f64)> for Geodesic {
         lat2: f64,
         lon2: f64,
     ) -> (f64, f64, f64, f64, f64, f64) {
-        let (a12, s12, azi1, azi2, _m12, M12, M21, _S12) =
-            self._gen_inverse_azi::<Distance<Azimuth<GeodesicScale<Empty>>>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Alp<inverse::M12M21<inverse::Arc>>> =
+            inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, azi1, azi2, M12, M21, a12)
+        (
+            out.get_s12(),
+            out.get_azi1(),
+            out.get_azi2(),
+            out.get_M12(),
+            out.get_M21(),
+            out.get_a12(),
+        )
     }
 }
 
@@ -1074,10 +1518,18 @@ impl InverseGeodesic<(f64, f64, f64, f64, f64, f64, f64)> for Geodesic {
         lat2: f64,
         lon2: f64,
     ) -> (f64, f64, f64, f64, f64, f64, f64) {
-        let (a12, s12, azi1, azi2, m12, M12, M21, _S12) =
-            self._gen_inverse_azi::<Distance<Azimuth<ReducedLength<GeodesicScale<Empty>>>>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Alp<inverse::M12<inverse::M12M21<inverse::Arc>>>> =
+            inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, azi1, azi2, m12, M12, M21, a12)
+        (
+            out.get_s12(),
+            out.get_azi1(),
+            out.get_azi2(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+            out.get_a12(),
+        )
     }
 }
 
@@ -1100,17 +1552,26 @@ impl InverseGeodesic<(f64, f64, f64, f64, f64, f64, f64, f64)> for Geodesic {
         lat2: f64,
         lon2: f64,
     ) -> (f64, f64, f64, f64, f64, f64, f64, f64) {
-        let (a12, s12, azi1, azi2, m12, M12, M21, S12) =
-            self._gen_inverse_azi::<Distance<Azimuth<ReducedLength<GeodesicScale<Area<Empty>>>>>>(lat1, lon1, lat2, lon2);
+        let out: inverse::Dist<inverse::Alp<inverse::M12<inverse::M12M21<inverse::Area<inverse::Arc>>>>> =
+            inverse_problem(self, lat1, lon1, lat2, lon2);
 
-        (s12, azi1, azi2, m12, M12, M21, S12, a12)
+        (
+            out.get_s12(),
+            out.get_azi1(),
+            out.get_azi2(),
+            out.get_m12(),
+            out.get_M12(),
+            out.get_M21(),
+            out.get_S12(),
+            out.get_a12(),
+        )
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::geodesic_line::GeodesicLine;
+    use crate::geodesic_line::{GeodesicLine, LinePosition};
     use approx::assert_relative_eq;
     use std::io::BufRead;
 
@@ -1954,6 +2415,31 @@ mod tests {
         assert_relative_eq!(lat2, -39.0, epsilon = 1.0);
         assert_relative_eq!(lon2, 105.0, epsilon = 1.0);
         assert_relative_eq!(azi2, -170.0, epsilon = 1.0);
+
+        // The same through the public APIs
+        use crate::traits::LongUnroll;
+        use crate::problem::direct::{Unroll, Azi, LatLon};
+        let r = geod.direct_result::<LongUnroll<Standard>>(40.0, -75.0, -10.0, 2e7);
+        assert_relative_eq!(r.lat2.unwrap(), -39.0, epsilon = 1.0);
+        assert_relative_eq!(r.lon2.unwrap(), -254.0, epsilon = 1.0);
+        assert_relative_eq!(r.azi2.unwrap(), -170.0, epsilon = 1.0);
+        let r = geod.direct_result::<Standard>(40.0, -75.0, -10.0, 2e7);
+        assert_relative_eq!(r.lon2.unwrap(), 105.0, epsilon = 1.0);
+
+        let out: Unroll<Azi<LatLon>> =
+            direct_problem(&geod, 40.0, -75.0, AzimuthOnly::from(-10.0), DistanceIn::from(2e7));
+        assert_relative_eq!(out.get_lat2(), -39.0, epsilon = 1.0);
+        assert_relative_eq!(out.get_lon2(), -254.0, epsilon = 1.0);
+        assert_relative_eq!(out.get_azi2(), -170.0, epsilon = 1.0);
+
+        let line = geod.line(40.0, -75.0, -10.0, caps::STANDARD | caps::DISTANCE_IN | caps::LONG_UNROLL);
+        let (lat2, lon2, azi2) = line.position(2e7);
+        assert_relative_eq!(lat2, -39.0, epsilon = 1.0);
+        assert_relative_eq!(lon2, -254.0, epsilon = 1.0);
+        assert_relative_eq!(azi2, -170.0, epsilon = 1.0);
+        let line = geod.line(40.0, -75.0, -10.0, caps::STANDARD | caps::DISTANCE_IN);
+        let (_lat2, lon2, _azi2) = line.position(2e7);
+        assert_relative_eq!(lon2, 105.0, epsilon = 1.0);
     }
 
     #[test]
@@ -1979,18 +2465,14 @@ mod tests {
     fn test_std_geodesic_geodsolve29() {
         // Check longitude unrolling with inverse calculation 2015-09-16
         let geod = Geodesic::wgs84();
-        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, _S12) =
-            geod._gen_inverse::<Standard>(0.0, 539.0, 0.0, 181.0);
-        // Note: This is also supposed to check adjusted longitudes, but geographiclib-rs
-        //       doesn't seem to support that as of 2021/01/18.
-        // assert_relative_eq!(lon1, 179, epsilon = 1e-10);
-        // assert_relative_eq!(lon2, -179, epsilon = 1e-10);
-        assert_relative_eq!(s12, 222639.0, epsilon = 0.5);
-        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, _S12) =
-            geod._gen_inverse::<Standard>(0.0, 539.0, 0.0, 181.0);
-        // assert_relative_eq!(lon1, 539, epsilon = 1e-10);
-        // assert_relative_eq!(lon2, 541, epsilon = 1e-10);
-        assert_relative_eq!(s12, 222639.0, epsilon = 0.5);
+        let r = geod.inverse_result::<Standard>(0.0, 539.0, 0.0, 181.0);
+        assert_relative_eq!(r.lon1.unwrap(), 179.0, epsilon = 1e-10);
+        assert_relative_eq!(r.lon2.unwrap(), -179.0, epsilon = 1e-10);
+        assert_relative_eq!(r.s12.unwrap(), 222639.0, epsilon = 0.5);
+        let r = geod.inverse_result::<crate::traits::LongUnroll<Standard>>(0.0, 539.0, 0.0, 181.0);
+        assert_relative_eq!(r.lon1.unwrap(), 539.0, epsilon = 1e-10);
+        assert_relative_eq!(r.lon2.unwrap(), 541.0, epsilon = 1e-10);
+        assert_relative_eq!(r.s12.unwrap(), 222639.0, epsilon = 0.5);
     }
 
     #[test]
@@ -2089,38 +2571,207 @@ mod tests {
         assert_relative_eq!(lat2, 45.30632, epsilon = 0.5e-5);
         assert_relative_eq!(lon2, -180.0, epsilon = 0.5e-5);
         assert_relative_eq!(azi2.abs(), 180.0, epsilon = 0.5e-5);
-        // geographiclib-rs does not appear to support Geodesic.inverse_line or
-        // or GeodesicLine.position as of 2021/01/18.
-        // let line = geod.inverse_line(45, 0, 80, -0.000000000000000003);
-        // let res = line.position(1e7, caps::STANDARD | caps::LONG_UNROLL);
-        // assert_relative_eq!(lat2, 45.30632, epsilon = 0.5e-5);
-        // assert_relative_eq!(lon2, -180, epsilon = 0.5e-5);
-        // assert_relative_eq!(azi2.abs(), 180, epsilon = 0.5e-5);
-    }
-
-    // #[test]
-    // fn test_std_geodesic_geodsolve65() {
-    //     // Check for bug in east-going check in GeodesicLine (needed to check for
-    //     // sign of 0) and sign error in area calculation due to a bogus override
-    //     // of the code for alp12.  Found/fixed on 2015-12-19.
-    //     // These tests rely on Geodesic.inverse_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
-
-    // #[test]
-    // fn test_std_geodesic_geodsolve69() {
-    //     // Check for InverseLine if line is slightly west of S and that s13 is
-    //     // correctly set.
-    //     // These tests rely on Geodesic.inverse_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
-
-    // #[test]
-    // fn test_std_geodesic_geodsolve71() {
-    //     // Check that DirectLine sets s13.
-    //     // These tests rely on Geodesic.direct_line, which is not supported by
-    //     // geographiclib-rs as of 2021/01/18.
-    // }
+        let line = geod.inverse_line(45.0, 0.0, 80.0, -0.000000000000000003, caps::ALL);
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            line._gen_position(false, 1e7, caps::STANDARD | caps::LONG_UNROLL);
+        assert_relative_eq!(lat2, 45.30632, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, -180.0, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2.abs(), 180.0, epsilon = 0.5e-5);
+    }
+
+    #[test]
+    fn test_std_geodesic_geodsolve65() {
+        // Check for bug in east-going check in GeodesicLine (needed to check for
+        // sign of 0) and sign error in area calculation due to a bogus override
+        // of the code for alp12.  Found/fixed on 2015-12-19.
+        let geod = Geodesic::wgs84();
+        let line = geod.inverse_line(30.0, -0.000000000000000001, -31.0, 180.0, caps::ALL);
+        assert_relative_eq!(line.latitude(), 30.0, epsilon = 0.5e-5);
+        assert_relative_eq!(line.longitude(), 0.0, epsilon = 0.5e-5);
+        assert_relative_eq!(line.azimuth().abs(), 180.0, epsilon = 0.5e-5);
+
+        let (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) =
+            line._gen_position(false, 1e7, caps::ALL | caps::LONG_UNROLL);
+        assert_relative_eq!(lat2, -60.23169, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, -0.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2.abs(), 180.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(s12, 10000000.0, epsilon = 0.5);
+        assert_relative_eq!(a12, 90.06544, epsilon = 0.5e-5);
+        assert_relative_eq!(m12, 6363636.0, epsilon = 0.5);
+        assert_relative_eq!(M12, -0.0012834, epsilon = 0.5e-7);
+        assert_relative_eq!(M21, 0.0013749, epsilon = 0.5e-7);
+        assert_relative_eq!(S12, 0.0, epsilon = 0.5);
+
+        let (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) =
+            line._gen_position(false, 2e7, caps::ALL | caps::LONG_UNROLL);
+        assert_relative_eq!(lat2, -30.03547, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, -180.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2, -0.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(s12, 20000000.0, epsilon = 0.5);
+        assert_relative_eq!(a12, 179.96459, epsilon = 0.5e-5);
+        assert_relative_eq!(m12, 54342.0, epsilon = 0.5);
+        assert_relative_eq!(M12, -1.0045592, epsilon = 0.5e-7);
+        assert_relative_eq!(M21, -0.9954339, epsilon = 0.5e-7);
+        assert_relative_eq!(S12, 127516405431022.0, epsilon = 0.5);
+    }
+
+    #[test]
+    fn test_std_geodesic_geodsolve69() {
+        // Check for InverseLine if line is slightly west of S and that s13 is
+        // correctly set.
+        let geod = Geodesic::wgs84();
+        let line = geod.inverse_line(-5.0, -0.000000000000002, -10.0, 180.0, caps::ALL);
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            line._gen_position(false, 2e7, caps::STANDARD | caps::LONG_UNROLL);
+        assert_relative_eq!(lat2, 4.96445, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, -180.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2, -0.00000, epsilon = 0.5e-5);
+
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) = line._gen_position(
+            false,
+            0.5 * line.distance(),
+            caps::STANDARD | caps::LONG_UNROLL,
+        );
+        assert_relative_eq!(lat2, -87.52461, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, -0.00000, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2, -180.00000, epsilon = 0.5e-5);
+    }
+
+    #[test]
+    fn test_direct_result() {
+        use crate::traits::{ArcMode, Latitude, Longitude};
+
+        let geod = Geodesic::wgs84();
+        let (lat2, lon2, azi2, m12, M12, M21, S12, a12) = geod.direct(40.64, -73.78, 45.0, 10e6);
+        let r = geod.direct_result::<All>(40.64, -73.78, 45.0, 10e6);
+        assert_eq!(
+            r,
+            DirectResult {
+                lat2: Some(lat2),
+                lon2: Some(lon2),
+                azi2: Some(azi2),
+                s12: Some(10e6),
+                a12,
+                m12: Some(m12),
+                M12: Some(M12),
+                M21: Some(M21),
+                S12: Some(S12),
+            }
+        );
+
+        let r = geod.direct_result::<Latitude<Longitude<Empty>>>(40.64, -73.78, 45.0, 10e6);
+        assert_eq!(r.lat2, Some(lat2));
+        assert_eq!(r.lon2, Some(lon2));
+        assert_eq!(r.azi2, None);
+        assert_eq!(r.s12, Some(10e6));
+        assert_eq!(r.M21, None);
+
+        let r = geod.direct_result::<ArcMode<Latitude<Longitude<Empty>>>>(40.64, -73.78, 45.0, 90.0);
+        assert_eq!(r.a12, 90.0);
+        assert_eq!(r.s12, None);
+        assert_relative_eq!(r.lat2.unwrap(), 32.589189911080098, epsilon = 1e-13);
+        assert_relative_eq!(r.lon2.unwrap(), 49.083659335557726, epsilon = 1e-13);
+
+        let r = geod.direct_result::<ArcMode<Distance<Empty>>>(40.64, -73.78, 45.0, 90.0);
+        assert_relative_eq!(r.s12.unwrap(), 10004591.827935437, epsilon = 1e-8);
+        assert_eq!(r.lat2, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let geod = Geodesic::new(6378388.0, 1.0 / 297.0);
+        let json = serde_json::to_string(&geod).unwrap();
+        assert_eq!(json, "[6378388.0,0.003367003367003367]");
+        let geod1: Geodesic = serde_json::from_str(&json).unwrap();
+        assert_eq!(geod1.a, geod.a);
+        assert_eq!(geod1.f, geod.f);
+        assert_eq!(geod1._c2, geod._c2);
+        let s12: f64 = geod.inverse(40.64, -73.78, 1.36, 103.99);
+        let s12_1: f64 = geod1.inverse(40.64, -73.78, 1.36, 103.99);
+        assert_eq!(s12, s12_1);
+
+        let r = geod.direct_result::<Standard>(40.64, -73.78, 45.0, 10e6);
+        let r1: DirectResult = serde_json::from_str(&serde_json::to_string(&r).unwrap()).unwrap();
+        assert_eq!(r, r1);
+        let r = geod.inverse_result::<Standard>(40.64, -73.78, 1.36, 103.99);
+        let json = serde_json::to_string(&r).unwrap();
+        assert!(json.contains("\"m12\":null"));
+        let r1: InverseResult = serde_json::from_str(&json).unwrap();
+        assert_eq!(r, r1);
+    }
+
+    #[test]
+    fn test_inverse_result() {
+        let geod = Geodesic::wgs84();
+        let (s12, azi1, azi2, m12, M12, M21, S12, a12) = geod.inverse(40.64, -73.78, 1.36, 103.99);
+        let r = geod.inverse_result::<All>(40.64, -73.78, 1.36, 103.99);
+        assert_eq!(
+            r,
+            InverseResult {
+                lon1: Some(-73.78),
+                lon2: Some(103.99),
+                s12: Some(s12),
+                azi1: Some(azi1),
+                azi2: Some(azi2),
+                a12,
+                m12: Some(m12),
+                M12: Some(M12),
+                M21: Some(M21),
+                S12: Some(S12),
+            }
+        );
+
+        let r = geod.inverse_result::<GeodesicScale<Empty>>(40.64, -73.78, 1.36, 103.99);
+        assert_eq!(r.s12, None);
+        assert_eq!(r.azi1, None);
+        assert_eq!(r.a12, a12);
+        assert_relative_eq!(r.M12.unwrap(), -0.73738609168138791, epsilon = 1e-13);
+        assert_relative_eq!(r.M21.unwrap(), -0.74354392401941605, epsilon = 1e-13);
+    }
+
+    #[test]
+    fn test_arc_direct() {
+        let geod = Geodesic::wgs84();
+        let (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) =
+            geod._gen_direct(40.64, -73.78, 45.0, true, 90.0, caps::ALL);
+        assert_eq!(a12, 90.0);
+
+        let (lat, lon) = geod.arc_direct(40.64, -73.78, 45.0, 90.0);
+        assert_eq!((lat, lon), (lat2, lon2));
+        let (lat, lon, azi, s) = geod.arc_direct(40.64, -73.78, 45.0, 90.0);
+        assert_eq!((lat, lon, azi, s), (lat2, lon2, azi2, s12));
+        let (_, _, _, s, m) = geod.arc_direct(40.64, -73.78, 45.0, 90.0);
+        assert_eq!((s, m), (s12, m12));
+        let (_, _, _, s, sc12, sc21) = geod.arc_direct(40.64, -73.78, 45.0, 90.0);
+        assert_eq!((s, sc12, sc21), (s12, M12, M21));
+        let (_, _, _, _, m, sc12, sc21, area): (f64, f64, f64, f64, f64, f64, f64, f64) =
+            geod.arc_direct(40.64, -73.78, 45.0, 90.0);
+        assert_eq!((m, sc12, sc21), (m12, M12, M21));
+        assert_relative_eq!(area, S12, epsilon = 1e-3);
+
+        assert_relative_eq!(s12, 10004591.827935437, epsilon = 1e-8);
+        // The distance found from the arc length gets back to the same point
+        let (lat, lon, azi) = geod.direct(40.64, -73.78, 45.0, s12);
+        assert_relative_eq!(lat, lat2, epsilon = 1e-12);
+        assert_relative_eq!(lon, lon2, epsilon = 1e-12);
+        assert_relative_eq!(azi, azi2, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_std_geodesic_geodsolve71() {
+        // Check that DirectLine sets s13.
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(1.0, 2.0, 45.0, 1e7, caps::ALL);
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) = line._gen_position(
+            false,
+            0.5 * line.distance(),
+            caps::STANDARD | caps::LONG_UNROLL,
+        );
+        assert_relative_eq!(lat2, 30.92625, epsilon = 0.5e-5);
+        assert_relative_eq!(lon2, 37.54640, epsilon = 0.5e-5);
+        assert_relative_eq!(azi2, 55.43104, epsilon = 0.5e-5);
+    }
 
     #[test]
     fn test_std_geodesic_geodsolve73() {
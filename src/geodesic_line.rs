@@ -6,8 +6,40 @@ use crate::cached_weights::{C1Coeff,C2Coeff,C1pCoeff};
 use crate::geodesic_capability as caps;
 use crate::geomath;
 use crate::internals::constants::{TINY};
-use std::collections::HashMap;
 
+/// A geodesic starting at a fixed point with a fixed azimuth.
+///
+/// Solving the direct problem repeatedly along the same geodesic redoes a
+/// good deal of setup work for every point. A `GeodesicLine` performs that
+/// setup once, so points along the line can be found cheaply with
+/// [`LinePosition::position`] and [`LineArcPosition::arc_position`].
+///
+/// The quantities which can be computed are controlled by the capabilities
+/// (see [`capability`](crate::capability)) the line was created with.
+/// `LATITUDE`, `AZIMUTH` and `LONG_UNROLL` are always included. `DISTANCE_IN`
+/// must be included for [`LinePosition::position`] to return anything other
+/// than `NaN`.
+///
+/// If the caps given when creating the line include `LONG_UNROLL`, the
+/// longitudes returned by [`LinePosition`], [`LineArcPosition`] and
+/// [`Waypoints`] are unrolled: rather than being reduced to [-180°, 180°],
+/// they change continuously along the line, so they tell how many times the
+/// geodesic has circled the ellipsoid.
+///
+/// ```rust
+/// // Sample points every 1000 km along the geodesic NE from JFK.
+/// use geographiclib_rs::{capability as caps, Geodesic, LinePosition};
+///
+/// let g = Geodesic::wgs84();
+/// let line = g.line(40.64, -73.78, 45.0, caps::STANDARD | caps::DISTANCE_IN);
+///
+/// let points: Vec<(f64, f64)> = (0..=10).map(|i| line.position(i as f64 * 1e6)).collect();
+///
+/// use approx::assert_relative_eq;
+/// let (lat, lon) = points[10];
+/// assert_relative_eq!(lat, 32.621100463725796);
+/// assert_relative_eq!(lon, 49.052487092959836);
+/// ```
 #[derive(Clone, Debug)]
 pub struct GeodesicLine<'a>{
     geod: &'a geodesic::Geodesic,
@@ -29,9 +61,18 @@ pub struct GeodesicLine<'a>{
     lat1: f64,
     lon1: f64,
     salp1: f64,
+    a13: f64,
+    s13: f64,
+    unroll: bool,
 }
 
 impl<'a> GeodesicLine<'a> {
+    /// Create a new line starting at (`lat1`, `lon1`) with azimuth `azi1`.
+    ///
+    /// `caps` defaults to `STANDARD | DISTANCE_IN`. If both `salp1` and `calp1`
+    /// are given they are used as the sine and cosine of `azi1` in place of
+    /// recomputing them from `azi1`. [`Geodesic::line`](crate::Geodesic::line)
+    /// is usually more convenient.
     pub fn new(
         geod: &'a geodesic::Geodesic,
         lat1: f64,
@@ -41,18 +82,10 @@ impl<'a> GeodesicLine<'a> {
         salp1: Option<f64>,
         calp1: Option<f64>,
     ) -> Self {
-        let caps = match caps {
-            None => caps::STANDARD | caps::DISTANCE_IN,
-            Some(caps) => caps,
-        };
-        let salp1 = match salp1 {
-            None => f64::NAN,
-            Some(salp1) => salp1,
-        };
-        let calp1 = match calp1 {
-            None => f64::NAN,
-            Some(calp1) => calp1,
-        };
+        let caps = caps.unwrap_or(caps::STANDARD | caps::DISTANCE_IN);
+        let unroll = caps & caps::LONG_UNROLL != 0;
+        let salp1 = salp1.unwrap_or(f64::NAN);
+        let calp1 = calp1.unwrap_or(f64::NAN);
 
         // This was taken from geodesic, putting it here for convenience
 
@@ -112,6 +145,9 @@ impl<'a> GeodesicLine<'a> {
             lon1,
             salp1,
             eps,
+            a13: f64::NAN,
+            s13: f64::NAN,
+            unroll,
         }
     }
 
@@ -289,48 +325,553 @@ impl<'a> GeodesicLine<'a> {
         (a12, lat2, lon2, azi2, s12, m12, M12, M21, S12)
     }
 
-    // not currently used, but maybe some day
-    #[allow(dead_code)]
-    pub fn Position(&self, s12: f64, outmask: Option<u64>) -> HashMap<String, f64> {
-        let outmask = match outmask {
-            Some(outmask) => outmask,
-            None => caps::STANDARD,
-        };
-        let mut result: HashMap<String, f64> = HashMap::new();
-        result.insert("lat1".to_string(), self.lat1);
-        result.insert("azi1".to_string(), self.azi1);
-        result.insert("s12".to_string(), s12);
-        let lon1 = if outmask & caps::LONG_UNROLL != 0 {
-            self.lon1
-        } else {
-            geomath::ang_normalize(self.lon1)
-        };
-        result.insert("lon1".to_string(), lon1);
+    /// Attach a reference point 3 to the line, given both its distance and arc length.
+    pub(in crate) fn with_end_point(mut self, s13: f64, a13: f64) -> Self {
+        self.s13 = s13;
+        self.a13 = a13;
+        self
+    }
 
-        let (a12, lat2, lon2, azi2, _s12, m12, M12, M21, S12) =
-            self._gen_position(false, s12, outmask);
-        let outmask = outmask & caps::OUT_MASK;
-        result.insert("a12".to_string(), a12);
-        if outmask & caps::LATITUDE != 0 {
-            result.insert("lat2".to_string(), lat2);
+    /// Set the distance (meters) to the reference point 3.
+    ///
+    /// The arc length to point 3 is updated to match. It is `NaN` if the line
+    /// was not created with the `DISTANCE_IN` capability.
+    pub fn set_distance(&mut self, s13: f64) {
+        self.s13 = s13;
+        let (a13, _lat2, _lon2, _azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(false, s13, caps::CAP_NONE);
+        self.a13 = a13;
+    }
+
+    /// Set the arc length (degrees) to the reference point 3.
+    ///
+    /// The distance to point 3 is updated to match. It is `NaN` if the line
+    /// was not created with the `DISTANCE` capability.
+    pub fn set_arc(&mut self, a13: f64) {
+        self.a13 = a13;
+        let (_a12, _lat2, _lon2, _azi2, s13, _m12, _M12, _M21, _S12) =
+            self._gen_position(true, a13, caps::DISTANCE);
+        self.s13 = s13;
+    }
+
+    /// Set either the distance (`arcmode = false`) or the arc length
+    /// (`arcmode = true`) to the reference point 3.
+    pub fn _gen_set_distance(&mut self, arcmode: bool, s13_a13: f64) {
+        if arcmode {
+            self.set_arc(s13_a13);
+        } else {
+            self.set_distance(s13_a13);
         }
-        if outmask & caps::LONGITUDE != 0 {
-            result.insert("lon2".to_string(), lon2);
+    }
+
+    /// Latitude of the first point (degrees).
+    pub fn latitude(&self) -> f64 {
+        self.lat1
+    }
+
+    /// Longitude of the first point (degrees).
+    pub fn longitude(&self) -> f64 {
+        self.lon1
+    }
+
+    /// Azimuth at the first point (degrees).
+    pub fn azimuth(&self) -> f64 {
+        self.azi1
+    }
+
+    /// Azimuth of the line where it crosses the equator (degrees), heading
+    /// north.
+    pub fn equatorial_azimuth(&self) -> f64 {
+        geomath::atan2d(self._salp0, self._calp0)
+    }
+
+    /// The capabilities this line was created with.
+    pub fn capabilities(&self) -> u64 {
+        self.caps
+    }
+
+    /// Whether longitudes are unrolled, see [`GeodesicLine`].
+    pub fn unroll(&self) -> bool {
+        self.unroll
+    }
+
+    // The outmask for the public position queries
+    fn position_mask(&self, outmask: u64) -> u64 {
+        if self.unroll {
+            outmask | caps::LONG_UNROLL
+        } else {
+            outmask
         }
-        if outmask & caps::AZIMUTH != 0 {
-            result.insert("azi2".to_string(), azi2);
+    }
+
+    /// Distance to the reference point 3 (meters).
+    ///
+    /// This is `NaN` unless the line was created with a reference point 3 (see
+    /// [`Geodesic::inverse_line`](crate::Geodesic::inverse_line) and
+    /// [`Geodesic::direct_line`](crate::Geodesic::direct_line)) or one was
+    /// set with [`GeodesicLine::set_distance`] or [`GeodesicLine::set_arc`].
+    pub fn distance(&self) -> f64 {
+        self.s13
+    }
+
+    /// Arc length to the reference point 3 (degrees).
+    ///
+    /// See [`GeodesicLine::distance`] for when this is set.
+    pub fn arc(&self) -> f64 {
+        self.a13
+    }
+
+    /// Iterate over `n + 1` points which split the line from point 1 to the
+    /// reference point 3 into `n` segments of equal length.
+    ///
+    /// `n` is taken to be at least 1, so both point 1 and point 3 are always
+    /// included, and at most `usize::MAX - 1`, so that the number of points
+    /// fits in a `usize`. The line must have the `DISTANCE_IN` capability and a
+    /// reference point 3 (see [`GeodesicLine::distance`]), otherwise the
+    /// points are `NaN`. Use [`Clone::clone`] to keep the line.
+    pub fn waypoints(self, n: usize) -> Waypoints<'a> {
+        let end = self.s13;
+        let n = n.max(1);
+        Waypoints::new(self, false, end / n as f64, end, n)
+    }
+
+    /// As [`GeodesicLine::waypoints`], but the segments are of equal arc length.
+    ///
+    /// This does not need the `DISTANCE_IN` capability, only a reference
+    /// point 3.
+    pub fn arc_waypoints(self, n: usize) -> Waypoints<'a> {
+        let end = self.a13;
+        let n = n.max(1);
+        Waypoints::new(self, true, end / n as f64, end, n)
+    }
+
+    /// Iterate over points every `ds` meters from point 1 to the reference
+    /// point 3.
+    ///
+    /// The last step is shortened so that point 3 is always the final point.
+    /// The requirements on the line are as for [`GeodesicLine::waypoints`].
+    ///
+    /// # Panics
+    /// Panics if `ds` is not positive and finite.
+    pub fn points_every(self, ds: f64) -> Waypoints<'a> {
+        assert!(
+            ds.is_finite() && ds > 0.0,
+            "GeodesicLine::points_every: spacing {} is not positive and finite",
+            ds
+        );
+        let end = self.s13;
+        Waypoints::new(self, false, ds.abs().copysign(end), end, Waypoints::steps(end, ds))
+    }
+
+    /// As [`GeodesicLine::points_every`], but with points every `da` degrees of
+    /// arc length.
+    ///
+    /// # Panics
+    /// Panics if `da` is not positive and finite.
+    pub fn arc_points_every(self, da: f64) -> Waypoints<'a> {
+        assert!(
+            da.is_finite() && da > 0.0,
+            "GeodesicLine::arc_points_every: spacing {} is not positive and finite",
+            da
+        );
+        let end = self.a13;
+        Waypoints::new(self, true, da.abs().copysign(end), end, Waypoints::steps(end, da))
+    }
+}
+
+/// An iterator over evenly spaced points along a [`GeodesicLine`].
+///
+/// This yields (lat, lon, azi) in degrees, from the first point of the line
+/// up to and including its reference point 3. All the points are computed
+/// from a single line, so the setup work of solving the direct problem is
+/// only done once.
+///
+/// Created by [`Geodesic::waypoints`](crate::Geodesic::waypoints),
+/// [`GeodesicLine::waypoints`], [`GeodesicLine::arc_waypoints`],
+/// [`GeodesicLine::points_every`] and [`GeodesicLine::arc_points_every`].
+#[derive(Clone, Debug)]
+pub struct Waypoints<'a> {
+    line: GeodesicLine<'a>,
+    arcmode: bool,
+    step: f64,
+    end: f64,
+    end_point: Option<(f64, f64)>,
+    index: usize,
+    count: usize,
+}
+
+impl<'a> Waypoints<'a> {
+    // n steps of size step, except the last which ends at end
+    fn new(line: GeodesicLine<'a>, arcmode: bool, step: f64, end: f64, n: usize) -> Self {
+        Waypoints {
+            line,
+            arcmode,
+            step,
+            end,
+            end_point: None,
+            index: 0,
+            count: n.min(usize::MAX - 1) + 1,
         }
-        if outmask & caps::REDUCEDLENGTH != 0 {
-            result.insert("m12".to_string(), m12);
+    }
+
+    // Number of steps of size at most step needed to cover end, saturating
+    // at usize::MAX for tiny steps. A NaN end (no point 3) gives 1.
+    fn steps(end: f64, step: f64) -> usize {
+        let n = (end / step).abs().ceil();
+        if n >= 1.0 {
+            n as usize
+        } else {
+            1
         }
-        if outmask & caps::GEODESICSCALE != 0 {
-            result.insert("M12".to_string(), M12);
-            result.insert("M21".to_string(), M21);
+    }
+
+    /// Replace the computed position of the final point with (lat, lon).
+    pub(in crate) fn with_end_point(mut self, lat: f64, lon: f64) -> Self {
+        self.end_point = Some((lat, lon));
+        self
+    }
+}
+
+impl<'a> Iterator for Waypoints<'a> {
+    type Item = (f64, f64, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.count {
+            return None;
         }
-        if outmask & caps::AREA != 0 {
-            result.insert("S12".to_string(), S12);
+        let last = self.index + 1 == self.count;
+        let s12_a12 = if last {
+            self.end
+        } else {
+            self.index as f64 * self.step
+        };
+        self.index += 1;
+
+        let outmask = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH;
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            self.line._gen_position(self.arcmode, s12_a12, self.line.position_mask(outmask));
+        match self.end_point {
+            Some((lat, lon)) if last => Some((lat, lon, azi2)),
+            _ => Some((lat2, lon2, azi2)),
         }
-        result
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Waypoints<'a> {}
+
+impl<'a> std::iter::FusedIterator for Waypoints<'a> {}
+
+/// Find the point a given distance along a [`GeodesicLine`].
+///
+/// # Arguments
+///   - s12 - Distance from the 1st point to the 2nd point [meters] Value may be negative
+///
+/// # Returns
+///
+/// As with [`DirectGeodesic`](crate::DirectGeodesic), only the outputs you ask for are
+/// calculated. The outputs are returned in the same order as the matching
+/// [`DirectGeodesic`](crate::DirectGeodesic) impl:
+///
+///  - lat2 latitude of point 2 (degrees).
+///  - lon2 longitude of point 2 (degrees).
+///  - azi2 (forward) azimuth at point 2 (degrees).
+///  - m12 reduced length of geodesic (meters).
+///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+///  - S12 area under the geodesic (meters<sup>2</sup>).
+///  - a12 arc length between point 1 and point 2 (degrees).
+///
+/// Any output the line was not created with the capability for is `NaN`.
+pub trait LinePosition<T> {
+    fn position(&self, s12: f64) -> T;
+}
+
+impl<'a> LinePosition<(f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    fn position(&self, s12: f64) -> (f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE;
+        let (_a12, lat2, lon2, _azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2)
+    }
+}
+
+impl<'a> LinePosition<(f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    fn position(&self, s12: f64) -> (f64, f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH;
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2)
+    }
+}
+
+impl<'a> LinePosition<(f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - m12 reduced length of geodesic (meters).
+    fn position(&self, s12: f64) -> (f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::REDUCEDLENGTH;
+        let (_a12, lat2, lon2, azi2, _s12, m12, _M12, _M21, _S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, m12)
+    }
+}
+
+impl<'a> LinePosition<(f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn position(&self, s12: f64) -> (f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::GEODESICSCALE;
+        let (_a12, lat2, lon2, azi2, _s12, _m12, M12, M21, _S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, M12, M21)
+    }
+}
+
+impl<'a> LinePosition<(f64, f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn position(&self, s12: f64) -> (f64, f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::REDUCEDLENGTH
+            | caps::GEODESICSCALE;
+        let (_a12, lat2, lon2, azi2, _s12, m12, M12, M21, _S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, m12, M12, M21)
+    }
+}
+
+impl<'a> LinePosition<(f64, f64, f64, f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LinePosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    ///  - S12 area under the geodesic (meters<sup>2</sup>).
+    ///  - a12 arc length between point 1 and point 2 (degrees).
+    fn position(&self, s12: f64) -> (f64, f64, f64, f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::REDUCEDLENGTH
+            | caps::GEODESICSCALE
+            | caps::AREA;
+        let (a12, lat2, lon2, azi2, _s12, m12, M12, M21, S12) =
+            self._gen_position(false, s12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, m12, M12, M21, S12, a12)
+    }
+}
+
+/// Find the point a given arc length along a [`GeodesicLine`].
+///
+/// # Arguments
+///   - a12 - Arc length from the 1st point to the 2nd point [degrees] Value may be negative
+///
+/// # Returns
+///
+/// Only the outputs you ask for are calculated. Since the arc length is the input here,
+/// the distance s12 takes its place in the outputs:
+///
+///  - lat2 latitude of point 2 (degrees).
+///  - lon2 longitude of point 2 (degrees).
+///  - azi2 (forward) azimuth at point 2 (degrees).
+///  - s12 distance between point 1 and point 2 (meters).
+///  - m12 reduced length of geodesic (meters).
+///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+///  - S12 area under the geodesic (meters<sup>2</sup>).
+///
+/// Any output the line was not created with the capability for is `NaN`.
+pub trait LineArcPosition<T> {
+    fn arc_position(&self, a12: f64) -> T;
+}
+
+impl<'a> LineArcPosition<(f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    fn arc_position(&self, a12: f64) -> (f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE;
+        let (_a12, lat2, lon2, _azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH;
+        let (_a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::DISTANCE;
+        let (_a12, lat2, lon2, azi2, s12, _m12, _M12, _M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, s12)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::DISTANCE
+            | caps::REDUCEDLENGTH;
+        let (_a12, lat2, lon2, azi2, s12, m12, _M12, _M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, s12, m12)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::DISTANCE
+            | caps::GEODESICSCALE;
+        let (_a12, lat2, lon2, azi2, s12, _m12, M12, M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, s12, M12, M21)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::DISTANCE
+            | caps::REDUCEDLENGTH
+            | caps::GEODESICSCALE;
+        let (_a12, lat2, lon2, azi2, s12, m12, M12, M21, _S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, s12, m12, M12, M21)
+    }
+}
+
+impl<'a> LineArcPosition<(f64, f64, f64, f64, f64, f64, f64, f64)> for GeodesicLine<'a> {
+    /// See the documentation for the LineArcPosition trait.
+    ///
+    /// # Returns
+    ///  - lat2 latitude of point 2 (degrees).
+    ///  - lon2 longitude of point 2 (degrees).
+    ///  - azi2 (forward) azimuth at point 2 (degrees).
+    ///  - s12 distance between point 1 and point 2 (meters).
+    ///  - m12 reduced length of geodesic (meters).
+    ///  - M12 geodesic scale of point 2 relative to point 1 (dimensionless).
+    ///  - M21 geodesic scale of point 1 relative to point 2 (dimensionless).
+    ///  - S12 area under the geodesic (meters<sup>2</sup>).
+    fn arc_position(&self, a12: f64) -> (f64, f64, f64, f64, f64, f64, f64, f64) {
+        let capabilities = caps::LATITUDE
+            | caps::LONGITUDE
+            | caps::AZIMUTH
+            | caps::DISTANCE
+            | caps::REDUCEDLENGTH
+            | caps::GEODESICSCALE
+            | caps::AREA;
+        let (_a12, lat2, lon2, azi2, s12, m12, M12, M21, S12) =
+            self._gen_position(true, a12, self.position_mask(capabilities));
+
+        (lat2, lon2, azi2, s12, m12, M12, M21, S12)
     }
 }
 
@@ -373,7 +914,8 @@ pub(in crate) fn area_calc(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use geodesic::Geodesic;
+    use approx::assert_relative_eq;
+    use geodesic::{DirectGeodesic, Geodesic};
 
     #[test]
     fn test_gen_position() {
@@ -391,6 +933,214 @@ mod tests {
         assert!(res.8.is_nan());
     }
 
+    #[test]
+    fn test_position_matches_direct() {
+        use crate::DirectGeodesic;
+
+        let geod = Geodesic::wgs84();
+        let line = geod.line(40.64, -73.78, 45.0, caps::ALL);
+        for s12 in [-5e6, 0.0, 1.0, 1e3, 1e6, 10e6, 19e6] {
+            let (lat2, lon2, azi2, m12, M12, M21, S12, a12) = line.position(s12);
+            let expected: (f64, f64, f64, f64, f64, f64, f64, f64) =
+                geod.direct(40.64, -73.78, 45.0, s12);
+            assert_eq!((lat2, lon2, azi2, m12, M12, M21, S12, a12), expected);
+        }
+    }
+
+    #[test]
+    fn test_arc_position() {
+        let geod = Geodesic::wgs84();
+        let line = geod.line(40.64, -73.78, 45.0, caps::ALL);
+        let (lat2, lon2, azi2, s12, m12, M12, M21, S12) = line.arc_position(90.0);
+        let (_a12, lat2_, lon2_, azi2_, s12_, m12_, M12_, M21_, S12_) =
+            geod._gen_direct(40.64, -73.78, 45.0, true, 90.0, caps::ALL);
+        assert_eq!(
+            (lat2, lon2, azi2, s12, m12, M12, M21, S12),
+            (lat2_, lon2_, azi2_, s12_, m12_, M12_, M21_, S12_)
+        );
+
+        // arc_position and position agree when given matching lengths
+        let (lat2, lon2, azi2) = line.position(s12);
+        assert_relative_eq!(lat2, lat2_, epsilon = 1e-13);
+        assert_relative_eq!(lon2, lon2_, epsilon = 1e-13);
+        assert_relative_eq!(azi2, azi2_, epsilon = 1e-13);
+    }
+
+    #[test]
+    fn test_position_without_distance_in() {
+        // A line without DISTANCE_IN can only be sampled by arc length
+        let geod = Geodesic::wgs84();
+        let line = geod.line(0.0, 0.0, 30.0, caps::STANDARD);
+        let (lat2, lon2): (f64, f64) = line.position(1e6);
+        assert!(lat2.is_nan());
+        assert!(lon2.is_nan());
+        let (lat2, lon2, _azi2, s12): (f64, f64, f64, f64) = line.arc_position(10.0);
+        assert!(lat2.is_finite());
+        assert!(lon2.is_finite());
+        assert!(s12 > 1e6);
+    }
+
+    #[test]
+    fn test_set_distance_and_arc() {
+        let geod = Geodesic::wgs84();
+        let mut line = geod.line(10.0, 20.0, 30.0, caps::STANDARD | caps::DISTANCE_IN);
+        assert!(line.distance().is_nan());
+        assert!(line.arc().is_nan());
+
+        line.set_distance(5e6);
+        assert_eq!(line.distance(), 5e6);
+        let a13 = line.arc();
+        assert_relative_eq!(a13, 45.0, epsilon = 0.5);
+
+        line.set_arc(a13);
+        assert_eq!(line.arc(), a13);
+        assert_relative_eq!(line.distance(), 5e6, epsilon = 1e-8);
+
+        line._gen_set_distance(false, 1e6);
+        assert_eq!(line.distance(), 1e6);
+        line._gen_set_distance(true, 1.0);
+        assert_eq!(line.arc(), 1.0);
+
+        // Without DISTANCE_IN the arc length can't be found from a distance
+        let mut line = geod.line(10.0, 20.0, 30.0, caps::LATITUDE);
+        line.set_distance(5e6);
+        assert_eq!(line.distance(), 5e6);
+        assert!(line.arc().is_nan());
+    }
+
+    #[test]
+    fn test_direct_line() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+        assert_eq!(line.distance(), 10e6);
+        let (a12, lat2, lon2, azi2, _s12, _m12, _M12, _M21, _S12) =
+            geod._gen_direct(40.64, -73.78, 45.0, false, 10e6, caps::STANDARD);
+        assert_eq!(line.arc(), a12);
+        let pos: (f64, f64, f64) = line.position(line.distance());
+        assert_eq!(pos, (lat2, lon2, azi2));
+
+        let line = geod.arc_direct_line(40.64, -73.78, 45.0, a12, caps::STANDARD);
+        assert_eq!(line.arc(), a12);
+        assert_relative_eq!(line.distance(), 10e6, epsilon = 1e-8);
+        // DISTANCE_IN is only added automatically for direct_line
+        assert_eq!(line.capabilities() & (caps::DISTANCE_IN & !caps::CAP_C1), 0);
+    }
+
+    #[test]
+    fn test_unroll() {
+        // See GeodSolve17
+        let geod = Geodesic::new(6.4e6, -1f64 / 150.0);
+        let line = geod.direct_line(40.0, -75.0, -10.0, 2e7, caps::STANDARD | caps::LONG_UNROLL);
+        assert!(line.unroll());
+        let (_lat2, lon2) = line.position(2e7);
+        assert_relative_eq!(lon2, -254.0, epsilon = 1.0);
+        let (_lat2, lon2) = line.arc_position(line.arc());
+        assert_relative_eq!(lon2, -254.0, epsilon = 1.0);
+
+        // The longitude decreases steadily along the whole line
+        let lons: Vec<f64> = line.points_every(1e5).map(|(_lat, lon, _azi)| lon).collect();
+        assert_relative_eq!(*lons.last().unwrap(), -254.0, epsilon = 1.0);
+        for pair in lons.windows(2) {
+            assert!(pair[1] < pair[0]);
+        }
+
+        let line = geod.direct_line(40.0, -75.0, -10.0, 2e7, caps::STANDARD);
+        assert!(!line.unroll());
+        let (_lat2, lon2) = line.position(2e7);
+        assert_relative_eq!(lon2, 105.0, epsilon = 1.0);
+    }
+
+    #[test]
+    fn test_waypoints() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+        let points: Vec<(f64, f64, f64)> = line.clone().waypoints(4).collect();
+        assert_eq!(points.len(), 5);
+        for (i, point) in points.iter().enumerate() {
+            let expected: (f64, f64, f64) = geod.direct(40.64, -73.78, 45.0, i as f64 * 2.5e6);
+            assert_relative_eq!(point.0, expected.0, epsilon = 1e-12);
+            assert_relative_eq!(point.1, expected.1, epsilon = 1e-12);
+            assert_relative_eq!(point.2, expected.2, epsilon = 1e-12);
+        }
+        assert_eq!(points[4], line.position(10e6));
+
+        // The last step is shortened to end at point 3
+        let mut points = line.clone().points_every(3e6);
+        assert_eq!(points.len(), 5);
+        assert_eq!(points.nth(1), Some(line.position(3e6)));
+        assert_eq!(points.last(), Some(line.position(10e6)));
+
+        // At least one segment, even for a zero length line
+        let line = geod.direct_line(40.64, -73.78, 45.0, 0.0, caps::STANDARD);
+        assert_eq!(line.clone().waypoints(0).count(), 2);
+        assert_eq!(line.points_every(1e3).count(), 2);
+    }
+
+    #[test]
+    fn test_waypoints_limits() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+
+        // The number of points saturates rather than overflowing
+        let mut points = line.clone().waypoints(usize::MAX);
+        assert_eq!(points.len(), usize::MAX);
+        assert_eq!(points.next(), Some(line.position(0.0)));
+        let mut points = line.clone().points_every(f64::MIN_POSITIVE);
+        assert_eq!(points.len(), usize::MAX);
+        assert_eq!(points.next(), Some(line.position(0.0)));
+        assert_eq!(line.clone().arc_points_every(f64::MIN_POSITIVE).len(), usize::MAX);
+
+        // A spacing larger than the line gives just the end points
+        assert_eq!(line.clone().points_every(f64::MAX).count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "not positive and finite")]
+    fn test_points_every_zero() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+        line.points_every(0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not positive and finite")]
+    fn test_points_every_negative() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+        line.points_every(-1e3);
+    }
+
+    #[test]
+    #[should_panic(expected = "not positive and finite")]
+    fn test_points_every_nan() {
+        let geod = Geodesic::wgs84();
+        let line = geod.direct_line(40.64, -73.78, 45.0, 10e6, caps::STANDARD);
+        line.points_every(f64::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "not positive and finite")]
+    fn test_arc_points_every_infinite() {
+        let geod = Geodesic::wgs84();
+        let line = geod.arc_direct_line(40.64, -73.78, 45.0, 90.0, caps::STANDARD);
+        line.arc_points_every(f64::INFINITY);
+    }
+
+    #[test]
+    fn test_arc_waypoints() {
+        let geod = Geodesic::wgs84();
+        let line = geod.arc_direct_line(40.64, -73.78, 45.0, 90.0, caps::STANDARD);
+        let points: Vec<(f64, f64, f64)> = line.clone().arc_waypoints(3).collect();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1], line.arc_position(30.0));
+        assert_eq!(points[3], line.arc_position(90.0));
+
+        let points: Vec<(f64, f64, f64)> = line.clone().arc_points_every(40.0).collect();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[2], line.arc_position(80.0));
+        assert_eq!(points[3], line.arc_position(90.0));
+    }
+
     #[test]
     fn test_init() {
         let geod = Geodesic::wgs84();
@@ -2,6 +2,7 @@
 #![allow(clippy::excessive_precision)]
 
 use crate::internals::utils::{constant_polyval};
+use std::f64::consts::FRAC_PI_2;
 
 // Normalize a two-vector
 pub fn norm(x: &mut f64, y: &mut f64) {
@@ -106,6 +107,17 @@ pub fn ang_diff(x: f64, y: f64) -> (f64, f64) {
     }
 }
 
+// The longitudes reported by the inverse problem, either reduced to
+// [-180,180] or with lon2 unrolled relative to lon1
+pub fn inverse_lons(lon1: f64, lon2: f64, unroll: bool) -> (f64, f64) {
+    if unroll {
+        let (lon12, e) = ang_diff(lon1, lon2);
+        (lon1, (lon1 + lon12) + e)
+    } else {
+        (ang_normalize(lon1), ang_normalize(lon2))
+    }
+}
+
 /// Compute sine and cosine of x in degrees
 pub fn sincosd(x: f64) -> (f64, f64) {
     // todo: replace this
@@ -133,6 +145,31 @@ pub fn sincosd(x: f64) -> (f64, f64) {
     (sinx, cosx)
 }
 
+// Compute sine and cosine of x + t in degrees, where t is a small
+// correction to x (e.g. the error term returned by ang_diff)
+pub fn sincosde(x: f64, t: f64) -> (f64, f64) {
+    let (r, q) = libm::remquo(x, 90.0);
+
+    let r = ang_round(r + t).to_radians();
+
+    let (mut sinx, mut cosx) = r.sin_cos();
+
+    (sinx, cosx) = match q as u32 & 3 {
+        0 => (sinx, cosx),
+        1 => (cosx, -sinx),
+        2 => (-sinx, -cosx),
+        3 => (-cosx, sinx),
+        _ => unreachable!(),
+    };
+
+    cosx += 0.0;
+
+    if sinx == 0.0 {
+        sinx = sinx.copysign(x);
+    }
+    (sinx, cosx)
+}
+
 // Compute atan2(y, x) with result in degrees
 pub fn atan2d(y: f64, x: f64) -> f64 {
     let mut x = x;
@@ -166,6 +203,49 @@ pub fn eatanhe(x: f64, es: f64) -> f64 {
     }
 }
 
+// tan(chi) given tau = tan(phi), where chi is the conformal latitude and es
+// is the signed eccentricity
+pub fn taupf(tau: f64, es: f64) -> f64 {
+    // Need this test, otherwise tau = +/-inf gives taup = nan
+    if tau.is_finite() {
+        let tau1 = 1.0_f64.hypot(tau);
+        let sig = eatanhe(tau / tau1, es).sinh();
+        1.0_f64.hypot(sig) * tau - sig * tau1
+    } else {
+        tau
+    }
+}
+
+// tan(phi) given taup = tan(chi), inverting taupf with Newton's method
+pub fn tauf(taup: f64, es: f64) -> f64 {
+    const NUMIT: usize = 5;
+    let tol = f64::EPSILON.sqrt() / 10.0;
+    let taumax = 2.0 / f64::EPSILON.sqrt();
+    let e2m = 1.0 - es * es.abs();
+    // To lowest order in e^2, taup = (1 - e^2) * tau, so use tau = taup/e2m
+    // as the starting guess. For large tau, taup = exp(-es*atanh(es)) * tau.
+    let mut tau = if taup.abs() > 70.0 {
+        taup * eatanhe(1.0, es).exp()
+    } else {
+        taup / e2m
+    };
+    let stol = tol * taup.abs().max(1.0);
+    // Handles +/-inf and nan
+    if tau.abs() >= taumax || tau.is_nan() {
+        return tau;
+    }
+    for _ in 0..NUMIT {
+        let taupa = taupf(tau, es);
+        let dtau = (taup - taupa) * (1.0 + e2m * tau * tau)
+            / (e2m * 1.0_f64.hypot(tau) * 1.0_f64.hypot(taupa));
+        tau += dtau;
+        if dtau.abs() < stol || dtau.is_nan() {
+            break;
+        }
+    }
+    tau
+}
+
 // Solve astroid equation
 pub fn astroid(x: f64, y: f64) -> f64 {
     let p = x.powi(2);
@@ -207,6 +287,240 @@ pub fn _A2m1f(eps: f64) -> f64 {
     (t - eps) / (1.0 + eps)
 }
 
+// Carlson's symmetric integral of the first kind, R_F(x, y, z)
+pub fn carlson_rf(x: f64, y: f64, z: f64) -> f64 {
+    // Carlson, eqs 2.2 - 2.7
+    let tol = (3.0 * f64::EPSILON * 0.01).powf(1.0 / 8.0);
+    let a0 = (x + y + z) / 3.0;
+    let q = (a0 - x).abs().max((a0 - y).abs()).max((a0 - z).abs()) / tol;
+    let (mut an, mut x0, mut y0, mut z0) = (a0, x, y, z);
+    let mut mul = 1.0;
+    // Max 6 trips
+    while q >= mul * an.abs() {
+        let lam = x0.sqrt() * y0.sqrt() + y0.sqrt() * z0.sqrt() + z0.sqrt() * x0.sqrt();
+        an = (an + lam) / 4.0;
+        x0 = (x0 + lam) / 4.0;
+        y0 = (y0 + lam) / 4.0;
+        z0 = (z0 + lam) / 4.0;
+        mul *= 4.0;
+    }
+    let xx = (a0 - x) / (mul * an);
+    let yy = (a0 - y) / (mul * an);
+    let zz = -(xx + yy);
+    let e2 = xx * yy - zz * zz;
+    let e3 = xx * yy * zz;
+    // https://dlmf.nist.gov/19.36.E1
+    (e3 * (6930.0 * e3 + e2 * (15015.0 * e2 - 16380.0) + 17160.0)
+        + e2 * ((10010.0 - 5775.0 * e2) * e2 - 24024.0)
+        + 240240.0)
+        / (240240.0 * an.sqrt())
+}
+
+// Carlson's symmetric integral of the second kind, R_D(x, y, z)
+pub fn carlson_rd(x: f64, y: f64, z: f64) -> f64 {
+    // Carlson, eqs 2.28 - 2.34
+    let tol = (0.2 * f64::EPSILON * 0.01).powf(1.0 / 8.0);
+    let a0 = (x + y + 3.0 * z) / 5.0;
+    let q = (a0 - x).abs().max((a0 - y).abs()).max((a0 - z).abs()) / tol;
+    let (mut an, mut x0, mut y0, mut z0) = (a0, x, y, z);
+    let mut mul = 1.0;
+    let mut s = 0.0;
+    // Max 7 trips
+    while q >= mul * an.abs() {
+        let lam = x0.sqrt() * y0.sqrt() + y0.sqrt() * z0.sqrt() + z0.sqrt() * x0.sqrt();
+        s += 1.0 / (mul * z0.sqrt() * (z0 + lam));
+        an = (an + lam) / 4.0;
+        x0 = (x0 + lam) / 4.0;
+        y0 = (y0 + lam) / 4.0;
+        z0 = (z0 + lam) / 4.0;
+        mul *= 4.0;
+    }
+    let xx = (a0 - x) / (mul * an);
+    let yy = (a0 - y) / (mul * an);
+    let zz = -(xx + yy) / 3.0;
+    let e2 = xx * yy - 6.0 * zz * zz;
+    let e3 = (3.0 * xx * yy - 8.0 * zz * zz) * zz;
+    let e4 = 3.0 * (xx * yy - zz * zz) * zz * zz;
+    let e5 = xx * yy * zz * zz * zz;
+    // https://dlmf.nist.gov/19.36.E2
+    ((471240.0 - 540540.0 * e2) * e5
+        + (612612.0 * e2 - 540540.0 * e3 - 556920.0) * e4
+        + e3 * (306306.0 * e3 + e2 * (675675.0 * e2 - 706860.0) + 680680.0)
+        + e2 * ((417690.0 - 255255.0 * e2) * e2 - 875160.0)
+        + 4084080.0)
+        / (4084080.0 * mul * an * an.sqrt())
+        + 3.0 * s
+}
+
+// The complete integrals R_F(x, y, 0) and R_G(x, y, 0), evaluated together
+// with the arithmetic-geometric mean
+pub fn carlson_rf_rg0(x: f64, y: f64) -> (f64, f64) {
+    // Carlson, eqs 2.36 - 2.39
+    let tol = 2.7 * (f64::EPSILON * 0.01).sqrt();
+    let x0 = x.max(y).sqrt();
+    let y0 = x.min(y).sqrt();
+    let (mut xn, mut yn) = (x0, y0);
+    let mut mul = 0.25;
+    let mut s = 0.0;
+    // Max 4 trips
+    while (xn - yn).abs() > tol * xn {
+        let t = (xn + yn) / 2.0;
+        yn = (xn * yn).sqrt();
+        xn = t;
+        mul *= 2.0;
+        s += mul * (xn - yn).powi(2);
+    }
+    let rf = std::f64::consts::PI / (xn + yn);
+    let rg = (((x0 + y0) / 2.0).powi(2) - s) * std::f64::consts::PI / (2.0 * (xn + yn));
+    (rf, rg)
+}
+
+/// Jacobi elliptic functions and the elliptic integrals of the first and
+/// second kind for a parameter `k2` = k<sup>2</sup> &le; 1.
+#[derive(Debug, Clone)]
+pub struct EllipticFunction {
+    k2: f64,
+    kp2: f64,
+    // K(k), E(k) and D(k) = (K(k) - E(k)) / k^2
+    kc: f64,
+    ec: f64,
+    dc: f64,
+}
+
+impl EllipticFunction {
+    pub fn new(k2: f64) -> Self {
+        let kp2 = 1.0 - k2;
+        let (kc, ec, dc) = if k2 == 0.0 {
+            (FRAC_PI_2, FRAC_PI_2, FRAC_PI_2 / 2.0)
+        } else if kp2 == 0.0 {
+            (f64::INFINITY, 1.0, f64::INFINITY)
+        } else {
+            let (rf, rg) = carlson_rf_rg0(kp2, 1.0);
+            (rf, 2.0 * rg, carlson_rd(0.0, kp2, 1.0) / 3.0)
+        };
+        EllipticFunction { k2, kp2, kc, ec, dc }
+    }
+
+    /// The parameter k<sup>2</sup>.
+    pub fn k2(&self) -> f64 {
+        self.k2
+    }
+
+    /// &Delta; = sqrt(1 - k<sup>2</sup> sin<sup>2</sup>&phi;) in terms of
+    /// `sn` = sin&phi; and `cn` = cos&phi;.
+    pub fn delta(&self, sn: f64, cn: f64) -> f64 {
+        if self.k2 < 0.0 {
+            (1.0 - self.k2 * sn * sn).sqrt()
+        } else {
+            (self.kp2 + self.k2 * cn * cn).sqrt()
+        }
+    }
+
+    /// The complete integral of the first kind, K(k).
+    pub fn k(&self) -> f64 {
+        self.kc
+    }
+
+    /// The complete integral of the second kind, E(k).
+    pub fn e(&self) -> f64 {
+        self.ec
+    }
+
+    /// K(k) - E(k), computed without cancellation.
+    pub fn ke(&self) -> f64 {
+        self.k2 * self.dc
+    }
+
+    /// The Jacobi elliptic functions (sn(x, k), cn(x, k), dn(x, k)).
+    pub fn sncndn(&self, x: f64) -> (f64, f64, f64) {
+        // Bulirsch's sncndn routine, p 89
+        const NUM: usize = 13;
+        let tol = (f64::EPSILON * 0.01).sqrt();
+        if self.kp2 == 0.0 {
+            let cn = 1.0 / x.cosh();
+            return (x.tanh(), cn, cn);
+        }
+        let mut mc = self.kp2;
+        let mut d = 0.0;
+        let mut x = x;
+        if self.kp2 < 0.0 {
+            d = 1.0 - mc;
+            mc /= -d;
+            d = d.sqrt();
+            x *= d;
+        }
+        let mut m = [0.0; NUM];
+        let mut n = [0.0; NUM];
+        let mut l = 0;
+        let mut a = 1.0;
+        let mut c = 0.0;
+        // This converges quadratically, max 5 trips
+        while l < NUM {
+            m[l] = a;
+            mc = mc.sqrt();
+            n[l] = mc;
+            c = (a + mc) / 2.0;
+            l += 1;
+            if (a - mc).abs() <= tol * a {
+                break;
+            }
+            mc *= a;
+            a = c;
+        }
+        x *= c;
+        let (mut sn, mut cn) = x.sin_cos();
+        let mut dn = 1.0;
+        if sn != 0.0 {
+            let mut a = cn / sn;
+            c *= a;
+            while l > 0 {
+                l -= 1;
+                let b = m[l];
+                a *= c;
+                c *= dn;
+                dn = (n[l] + a) / (b + a);
+                a = c / b;
+            }
+            a = 1.0 / (c * c + 1.0).sqrt();
+            sn = if sn.is_sign_negative() { -a } else { a };
+            cn = c * sn;
+            if self.kp2 < 0.0 {
+                std::mem::swap(&mut cn, &mut dn);
+                sn /= d;
+            }
+        }
+        (sn, cn, dn)
+    }
+
+    /// The incomplete integral of the second kind, E(&phi;, k), in terms of
+    /// the Jacobi elliptic functions of K(&phi;, k).
+    pub fn e_sncndn(&self, sn: f64, cn: f64, dn: f64) -> f64 {
+        let (cn2, dn2, sn2) = (cn * cn, dn * dn, sn * sn);
+        let mut ei = if cn2 != 0.0 {
+            sn.abs()
+                * if self.k2 <= 0.0 {
+                    // Carlson, eq. 4.6 and https://dlmf.nist.gov/19.25.E9
+                    carlson_rf(cn2, dn2, 1.0) - self.k2 * sn2 * carlson_rd(cn2, dn2, 1.0) / 3.0
+                } else if self.kp2 >= 0.0 {
+                    // https://dlmf.nist.gov/19.25.E10
+                    self.kp2 * carlson_rf(cn2, dn2, 1.0)
+                        + self.k2 * self.kp2 * sn2 * carlson_rd(cn2, 1.0, dn2) / 3.0
+                        + self.k2 * cn.abs() / dn
+                } else {
+                    // https://dlmf.nist.gov/19.25.E11
+                    -self.kp2 * sn2 * carlson_rd(dn2, 1.0, cn2) / 3.0 + dn / cn.abs()
+                }
+        } else {
+            self.e()
+        };
+        // Enforce the usual trig-like symmetries
+        if cn.is_sign_negative() {
+            ei = 2.0 * self.e() - ei;
+        }
+        ei.copysign(sn)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,11 +551,64 @@ mod tests {
         assert_eq!(_A1m1f(0.12), 0.1404582405272727);
     }
 
+    #[test]
+    fn test_taupf_tauf() {
+        // oblate and prolate ellipsoids
+        for &es in [0.0818191908426215, -0.1].iter() {
+            for &tau in [0.0, 1e-3, 0.5, 1.0, 10.0, 1e3, 1e9].iter() {
+                let taup = taupf(tau, es);
+                assert!((tauf(taup, es) - tau).abs() <= 4.0 * f64::EPSILON * tau.max(1.0));
+                assert_eq!(tauf(-taup, es), -tauf(taup, es));
+            }
+            assert_eq!(taupf(f64::INFINITY, es), f64::INFINITY);
+            assert_eq!(tauf(f64::INFINITY, es), f64::INFINITY);
+        }
+        assert_eq!(taupf(1.0, 0.0), 1.0);
+    }
+
     #[test]
     fn test_astroid() {
         assert_eq!(astroid(21.0, 12.0), 23.44475767500982);
     }
 
+    #[test]
+    fn test_elliptic_function() {
+        assert_relative_eq!(carlson_rf(1.0, 2.0, 3.0), 0.7269459354689082, epsilon = 1e-15);
+        assert_relative_eq!(carlson_rd(1.0, 2.0, 3.0), 0.2904602810289906, epsilon = 1e-15);
+
+        // Values from mpmath, with E(x) the integral of dn^2 from 0 to x
+        let cases = [
+            (0.5, 1.8540746773013719, 1.3506438810476755, [
+                (0.7, 0.62434009096621735, 0.78115264245363431, 0.89727349532132494, 0.65020750554333838),
+                (-2.5, -0.89061518822609436, -0.45475772286020445, 0.77678973554656299, -1.6959808018739937),
+            ]),
+            (-0.3, 1.4694917220921213, 1.6826930354888961, [
+                (0.7, 0.65608167645420500, 0.75468989248633764, 1.0626066769288098, 0.73183466767198970),
+                (-2.5, -0.42870873817500825, -0.90344275845921319, 1.0271987902330699, -2.9181761654063448),
+            ]),
+        ];
+        for &(k2, k, e, points) in cases.iter() {
+            let ell = EllipticFunction::new(k2);
+            assert_relative_eq!(ell.k(), k, epsilon = 1e-15);
+            assert_relative_eq!(ell.e(), e, epsilon = 1e-15);
+            assert_relative_eq!(ell.ke(), k - e, epsilon = 1e-15);
+            for &(x, sn0, cn0, dn0, e0) in points.iter() {
+                let (sn, cn, dn) = ell.sncndn(x);
+                assert_relative_eq!(sn, sn0, epsilon = 1e-15);
+                assert_relative_eq!(cn, cn0, epsilon = 1e-15);
+                assert_relative_eq!(dn, dn0, epsilon = 1e-15);
+                assert_relative_eq!(ell.e_sncndn(sn, cn, dn), e0, epsilon = 1e-14);
+            }
+        }
+
+        // The circular and hyperbolic limits
+        let ell = EllipticFunction::new(0.0);
+        assert_eq!(ell.k(), FRAC_PI_2);
+        assert_eq!(ell.sncndn(0.5), (0.5_f64.sin(), 0.5_f64.cos(), 1.0));
+        let ell = EllipticFunction::new(1.0);
+        assert_eq!(ell.sncndn(0.5), (0.5_f64.tanh(), 1.0 / 0.5_f64.cosh(), 1.0 / 0.5_f64.cosh()));
+    }
+
     // corresponding to tests/signtest.cpp
     mod sign_test {
         use super::*;
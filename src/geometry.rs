@@ -0,0 +1,163 @@
+//! Geodesic measurements of [`geo-types`](https://docs.rs/geo-types) geometries.
+//!
+//! Enabled with the `geo-types` feature. Coordinates are in degrees with `x`
+//! the longitude and `y` the latitude.
+//!
+//! The length and area functions are generic over [`PolygonEdge`], so they
+//! give geodesic measurements when passed a [`Geodesic`] and rhumb line
+//! measurements when passed a [`Rhumb`](crate::Rhumb).
+//!
+//! # Example
+//! ```rust
+//! use geo_types::{line_string, polygon, Point};
+//! use geographiclib_rs::{geometry, Geodesic, Winding};
+//!
+//! let g = Geodesic::wgs84();
+//!
+//! let jfk = Point::new(-73.78, 40.64);
+//! let lhr = Point::new(-0.45, 51.47);
+//! let distance = geometry::distance(g, &jfk, &lhr);
+//!
+//! let track = line_string![(x: -73.78, y: 40.64), (x: -0.45, y: 51.47)];
+//! let length = geometry::line_string_length(g, &track);
+//!
+//! use approx::assert_relative_eq;
+//! assert_relative_eq!(length, distance, epsilon = 1e-8);
+//!
+//! let square = polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0), (x: 0.0, y: 1.0)];
+//! let (perimeter, area) = geometry::polygon_area(g, &square, Winding::CounterClockwise);
+//! assert_relative_eq!(perimeter, 443770.917248302, epsilon = 1e-6);
+//! assert_relative_eq!(area, 12308778361.469452, epsilon = 1e-3);
+//! ```
+
+use geo_types::{Coord, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+
+use crate::{Geodesic, InverseGeodesic, MultiPolygonArea, PolygonEdge, Polyline, Winding};
+
+/// The geodesic distance (meters) between two points.
+pub fn distance(geod: &Geodesic, p1: &Point<f64>, p2: &Point<f64>) -> f64 {
+    geod.inverse(p1.y(), p1.x(), p2.y(), p2.x())
+}
+
+/// The length (meters) of a line string.
+pub fn line_string_length<G: PolygonEdge>(geod: &G, line_string: &LineString<f64>) -> f64 {
+    let mut pl = Polyline::with_edge(geod);
+    for c in line_string.coords() {
+        pl.add_point(c.y, c.x);
+    }
+    pl.compute().0
+}
+
+/// The total length (meters) of the line strings of a multi line string.
+pub fn multi_line_string_length<G: PolygonEdge>(geod: &G, multi_line_string: &MultiLineString<f64>) -> f64 {
+    multi_line_string
+        .iter()
+        .map(|line_string| line_string_length(geod, line_string))
+        .sum()
+}
+
+/// The perimeter (meters) and area (meters²) of a polygon.
+///
+/// `winding` is the winding of the exterior ring, the holes are wound the
+/// opposite way, see [`MultiPolygonArea`]. The area is negative if the rings
+/// are wound against this convention. The perimeter includes the holes.
+pub fn polygon_area<G: PolygonEdge>(geod: &G, polygon: &Polygon<f64>, winding: Winding) -> (f64, f64) {
+    let mut mpa = MultiPolygonArea::with_edge(geod, winding);
+    add_polygon(&mut mpa, polygon);
+    let (perimeter, area, _num) = mpa.compute(true);
+    (perimeter, area)
+}
+
+/// The total perimeter (meters) and area (meters²) of the polygons of a
+/// multi polygon, see [`polygon_area`].
+pub fn multi_polygon_area<G: PolygonEdge>(
+    geod: &G,
+    multi_polygon: &MultiPolygon<f64>,
+    winding: Winding,
+) -> (f64, f64) {
+    let mut mpa = MultiPolygonArea::with_edge(geod, winding);
+    for polygon in multi_polygon.iter() {
+        add_polygon(&mut mpa, polygon);
+    }
+    let (perimeter, area, _num) = mpa.compute(true);
+    (perimeter, area)
+}
+
+fn add_polygon<G: PolygonEdge>(mpa: &mut MultiPolygonArea<'_, G>, polygon: &Polygon<f64>) {
+    mpa.add_exterior(ring_points(polygon.exterior()));
+    for interior in polygon.interiors() {
+        mpa.add_interior(ring_points(interior));
+    }
+}
+
+// The (lat, lon) points of a ring, dropping the repeated closing point
+fn ring_points(ring: &LineString<f64>) -> impl Iterator<Item = (f64, f64)> + '_ {
+    let n = if ring.is_closed() && !ring.0.is_empty() {
+        ring.0.len() - 1
+    } else {
+        ring.0.len()
+    };
+    ring.0[..n].iter().map(|&Coord { x, y }| (y, x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PolygonArea, Rhumb};
+    use approx::assert_relative_eq;
+    use geo_types::{line_string, polygon};
+
+    #[test]
+    fn test_lengths() {
+        let geod = Geodesic::wgs84();
+        let ls = line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0)];
+        let s1: f64 = geod.inverse(0.0, 0.0, 0.0, 1.0);
+        let s2: f64 = geod.inverse(0.0, 1.0, 1.0, 1.0);
+        assert_relative_eq!(line_string_length(geod, &ls), s1 + s2, epsilon = 1e-8);
+
+        let mls = MultiLineString(vec![ls.clone(), ls]);
+        assert_relative_eq!(multi_line_string_length(geod, &mls), 2.0 * (s1 + s2), epsilon = 1e-8);
+        assert_eq!(line_string_length(geod, &LineString::new(vec![])), 0.0);
+
+        // Along the equator rhumb lines and geodesics agree
+        let rhumb = Rhumb::new(geod);
+        let eq = line_string![(x: 0.0, y: 0.0), (x: 10.0, y: 0.0)];
+        assert_relative_eq!(line_string_length(&rhumb, &eq), line_string_length(geod, &eq), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_areas() {
+        let geod = Geodesic::wgs84();
+        let poly = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 2.0, y: 0.0), (x: 2.0, y: 2.0), (x: 0.0, y: 2.0)],
+            interiors: [[(x: 0.5, y: 0.5), (x: 0.5, y: 1.5), (x: 1.5, y: 1.5), (x: 1.5, y: 0.5)]],
+        );
+        let mut mpa = MultiPolygonArea::new(geod, Winding::CounterClockwise);
+        mpa.add_exterior([(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)]);
+        mpa.add_interior([(0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (0.5, 1.5)]);
+        let (perimeter, area, _) = mpa.compute(true);
+
+        let (p, a) = polygon_area(geod, &poly, Winding::CounterClockwise);
+        assert_relative_eq!(p, perimeter, epsilon = 1e-8);
+        assert_relative_eq!(a, area, epsilon = 1e-3);
+        let (_, a) = polygon_area(geod, &poly, Winding::Clockwise);
+        assert_relative_eq!(a, -area, epsilon = 1e-3);
+
+        let far = polygon![(x: 179.0, y: -10.0), (x: -179.0, y: -10.0), (x: -179.0, y: -9.0), (x: 179.0, y: -9.0)];
+        let mut pa = PolygonArea::new(geod, Winding::CounterClockwise);
+        for &(lat, lon) in [(-10.0, 179.0), (-10.0, -179.0), (-9.0, -179.0), (-9.0, 179.0)].iter() {
+            pa.add_point(lat, lon);
+        }
+        let (p_far, a_far, _) = pa.compute(true);
+        let (p, a) = multi_polygon_area(geod, &MultiPolygon(vec![poly, far]), Winding::CounterClockwise);
+        assert_relative_eq!(p, perimeter + p_far, epsilon = 1e-8);
+        assert_relative_eq!(a, area + a_far, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_distance() {
+        let geod = Geodesic::wgs84();
+        let s12: f64 = geod.inverse(40.64, -73.78, 1.36, 103.99);
+        assert_eq!(distance(geod, &Point::new(-73.78, 40.64), &Point::new(103.99, 1.36)), s12);
+    }
+}
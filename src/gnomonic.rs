@@ -0,0 +1,204 @@
+use crate::geodesic_capability as caps;
+use crate::geomath;
+use crate::problem::inverse::{self, inverse_problem, InverseProblemReturnValue};
+use crate::Geodesic;
+
+const NUMIT: usize = 10;
+
+/// The ellipsoidal gnomonic projection.
+///
+/// The projection is centered on (`lat0`, `lon0`). A point at distance
+/// `rho = m12 / M12` along the geodesic from the center with azimuth `azi0`
+/// is mapped to `x = rho * sin(azi0)`, `y = rho * cos(azi0)`, where `m12` and
+/// `M12` are the reduced length and geodesic scale of the geodesic. On a
+/// sphere this is the usual gnomonic projection, which maps great circles to
+/// straight lines. On an ellipsoid geodesics are very nearly straight lines
+/// near the center, which makes the projection useful for finding the
+/// intersection of two geodesics or the point on a geodesic closest to a given
+/// point, by iterating with the center at the current estimate.
+///
+/// Points more than 90&deg; (roughly) from the center can't be projected, and
+/// give NaNs. See C. F. F. Karney,
+/// [Algorithms for geodesics](https://doi.org/10.1007/s00190-012-0578-z),
+/// J. Geodesy 87, 43&ndash;55 (2013), &sect;8.
+#[derive(Debug, Clone)]
+pub struct Gnomonic<'a> {
+    geod: &'a Geodesic,
+    eps: f64,
+}
+
+/// Gnomonic maps between geographic coordinates and the gnomonic projection
+/// centered on a given point.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, Gnomonic};
+///
+/// let g = Geodesic::wgs84();
+/// let proj = Gnomonic::new(g);
+///
+/// // Calais in the projection centered on Paris
+/// let (lat0, lon0) = (48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+/// let (x, y, _azi, _rk) = proj.forward(lat0, lon0, 50.9, 1.8);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x, -37543.66988338346, epsilon = 1e-6);
+/// assert_relative_eq!(y, 230103.21385030495, epsilon = 1e-6);
+///
+/// let (lat, lon, _azi, _rk) = proj.reverse(lat0, lon0, x, y);
+/// assert_relative_eq!(lat, 50.9, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 1.8, epsilon = 1e-12);
+/// ```
+impl<'a> Gnomonic<'a> {
+    /// Create a new Gnomonic projection using a Geodesic.
+    pub fn new(geod: &'a Geodesic) -> Self {
+        Gnomonic {
+            geod,
+            eps: 0.01 * f64::EPSILON.sqrt(),
+        }
+    }
+
+    /// Project (`lat`, `lon`) onto the plane centered on (`lat0`, `lon0`).
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - azi azimuth of the geodesic through the point (degrees).
+    /// - rk reciprocal of the azimuthal scale at the point.
+    ///
+    /// If the point is too far from the center, `rk` is not positive and `x`
+    /// and `y` are NaN.
+    pub fn forward(&self, lat0: f64, lon0: f64, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let out: inverse::Alp<inverse::M12<inverse::M12M21<inverse::Arc>>> =
+            inverse_problem(self.geod, lat0, lon0, lat, lon);
+        let rk = out.get_M12();
+        let (x, y) = if rk <= 0.0 {
+            (f64::NAN, f64::NAN)
+        } else {
+            let rho = out.get_m12() / rk;
+            let (x, y) = geomath::sincosd(out.get_azi1());
+            (x * rho, y * rho)
+        };
+        (x, y, out.get_azi2(), rk)
+    }
+
+    /// Find the point which (`x`, `y`) in the projection centered on
+    /// (`lat0`, `lon0`) came from.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees).
+    /// - azi azimuth of the geodesic through the point (degrees).
+    /// - rk reciprocal of the azimuthal scale at the point.
+    ///
+    /// The distance along the geodesic from the center is found with
+    /// Newton's method. If this fails to converge all the results are NaN.
+    pub fn reverse(&self, lat0: f64, lon0: f64, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let a = self.geod.equatorial_radius();
+        let azi0 = geomath::atan2d(x, y);
+        let mut rho = x.hypot(y);
+        let mut s = a * (rho / a).atan();
+        let little = rho <= a;
+        if !little {
+            rho = 1.0 / rho;
+        }
+        let line = self.geod.line(
+            lat0,
+            lon0,
+            azi0,
+            caps::LATITUDE
+                | caps::LONGITUDE
+                | caps::AZIMUTH
+                | caps::DISTANCE_IN
+                | caps::REDUCEDLENGTH
+                | caps::GEODESICSCALE,
+        );
+        let mask = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH | caps::REDUCEDLENGTH | caps::GEODESICSCALE;
+        let mut trip = false;
+        for _ in 0..NUMIT {
+            #[allow(non_snake_case)]
+            let (_, lat, lon, azi, _, m, M, _, _) = line._gen_position(false, s, mask);
+            if trip {
+                return (lat, lon, azi, M);
+            }
+            // If little, solve rho(s) = rho with drho(s)/ds = 1/M^2,
+            // else solve 1/rho(s) = 1/rho with d(1/rho(s))/ds = -1/m^2
+            let ds = if little { (m - rho * M) * M } else { (rho * m - M) * m };
+            s -= ds;
+            // One more step once converged; this also escapes with NaNs
+            if ds.abs() < self.eps * a || ds.is_nan() {
+                trip = true;
+            }
+        }
+        (f64::NAN, f64::NAN, f64::NAN, f64::NAN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        let geod = Geodesic::wgs84();
+        let proj = Gnomonic::new(geod);
+        let (lat0, lon0) = (48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+
+        let (x, y, azi, rk) = proj.forward(lat0, lon0, 50.9, 1.8);
+        assert_relative_eq!(x, -37543.66988338346, epsilon = 1e-6);
+        assert_relative_eq!(y, 230103.21385030495, epsilon = 1e-6);
+        assert_relative_eq!(azi, -9.6745577189165335, epsilon = 1e-12);
+        assert_relative_eq!(rk, 0.99933327051713972, epsilon = 1e-14);
+
+        let (lat, lon, azi2, rk2) = proj.reverse(lat0, lon0, x, y);
+        assert_relative_eq!(lat, 50.9, epsilon = 1e-12);
+        assert_relative_eq!(lon, 1.8, epsilon = 1e-12);
+        assert_relative_eq!(azi2, azi, epsilon = 1e-9);
+        assert_relative_eq!(rk2, rk, epsilon = 1e-12);
+
+        // Far from the center
+        for &(lat, lon) in [(10.0, 60.0), (-20.0, -40.0), (80.0, 150.0)].iter() {
+            let (x, y, _, _) = proj.forward(lat0, lon0, lat, lon);
+            assert!(x.hypot(y) > geod.equatorial_radius());
+            let (lat1, lon1, _, _) = proj.reverse(lat0, lon0, x, y);
+            assert_relative_eq!(lat1, lat, epsilon = 1e-10);
+            assert_relative_eq!(lon1, lon, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_forward_out_of_range() {
+        let geod = Geodesic::wgs84();
+        let proj = Gnomonic::new(geod);
+        let (x, y, _azi, rk) = proj.forward(48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0, -30.0, 120.0);
+        assert_relative_eq!(rk, -0.6396749351962725, epsilon = 1e-14);
+        assert!(x.is_nan() && y.is_nan());
+    }
+
+    #[test]
+    fn test_geodesics_are_nearly_straight() {
+        // Points along a geodesic near the center lie close to a line
+        let geod = Geodesic::wgs84();
+        let proj = Gnomonic::new(geod);
+        let (lat0, lon0) = (30.0, 0.0);
+        let line = geod.inverse_line(25.0, -5.0, 35.0, 8.0, caps::STANDARD | caps::DISTANCE_IN);
+        let ds = line.distance() / 4.0;
+        let pts: Vec<(f64, f64)> = (0..=4)
+            .map(|i| {
+                let (_, lat, lon, _, _, _, _, _, _) =
+                    line._gen_position(false, ds * f64::from(i), caps::LATITUDE | caps::LONGITUDE);
+                let (x, y, _, _) = proj.forward(lat0, lon0, lat, lon);
+                (x, y)
+            })
+            .collect();
+        let (x0, y0) = pts[0];
+        let (x4, y4) = pts[4];
+        let len = (x4 - x0).hypot(y4 - y0);
+        for &(x, y) in pts.iter() {
+            // Offset from the chord
+            let off = ((x - x0) * (y4 - y0) - (y - y0) * (x4 - x0)).abs() / len;
+            assert!(off < 1e-7 * len);
+        }
+    }
+}
@@ -82,3 +82,59 @@ pub (in crate) const C1PF_COEFF: [f64; COEFF_SIZE] = [
     3467.0, 7680.0,
     38081.0, 61440.0,
 ];
+
+/// Size of the packed coefficient arrays for the series in the third
+/// flattening `n` used by the rhumb line and transverse Mercator code.
+pub (in crate) const AUX_COEFF_SIZE: usize = 27_usize;
+
+/// Krüger series for the rectifying latitude in terms of the conformal
+/// latitude, `mu = chi + sum(alp[j] * sin(2*j*chi))`
+pub (in crate) const ALP_COEFF: [f64; AUX_COEFF_SIZE] = [
+    // alp[1]/n^1
+    31564.0, -66675.0, 34440.0, 47250.0, -100800.0, 75600.0, 151200.0,
+    // alp[2]/n^2
+    -1983433.0, 863232.0, 748608.0, -1161216.0, 524160.0, 1935360.0,
+    // alp[3]/n^3
+    670412.0, 406647.0, -533952.0, 184464.0, 725760.0,
+    // alp[4]/n^4
+    6601661.0, -7732800.0, 2230245.0, 7257600.0,
+    // alp[5]/n^5
+    -13675556.0, 3438171.0, 7983360.0,
+    // alp[6]/n^6
+    212378941.0, 319334400.0,
+];
+
+/// Krüger series for the conformal latitude in terms of the rectifying
+/// latitude, `chi = mu - sum(bet[j] * sin(2*j*mu))`
+pub (in crate) const BET_COEFF: [f64; AUX_COEFF_SIZE] = [
+    // bet[1]/n^1
+    384796.0, -382725.0, -6720.0, 932400.0, -1612800.0, 1209600.0, 2419200.0,
+    // bet[2]/n^2
+    -1118711.0, 1695744.0, -1174656.0, 258048.0, 80640.0, 3870720.0,
+    // bet[3]/n^3
+    22276.0, -16929.0, -15984.0, 12852.0, 362880.0,
+    // bet[4]/n^4
+    -830251.0, -158400.0, 197865.0, 7257600.0,
+    // bet[5]/n^5
+    -435388.0, 453717.0, 15966720.0,
+    // bet[6]/n^6
+    20648693.0, 638668800.0,
+];
+
+/// Series for the area under a rhumb line,
+/// `integral(sin(xi), psi) = log(cosh(psi)) + sum(R[l] * cos(2*l*chi))`
+/// where `xi` is the authalic latitude
+pub (in crate) const RHUMB_R_COEFF: [f64; AUX_COEFF_SIZE] = [
+    // R[1]/n^1
+    -114456994.0, 56868630.0, 79819740.0, -240540300.0, 312161850.0, -212837625.0, 638512875.0,
+    // R[2]/n^2
+    51304574.0, 24731070.0, -78693615.0, 71621550.0, -28378350.0, 212837625.0,
+    // R[3]/n^3
+    1554472.0, -6282003.0, 4684680.0, -1396395.0, 14189175.0,
+    // R[4]/n^4
+    -4913956.0, 3205800.0, -791505.0, 8108100.0,
+    // R[5]/n^5
+    1092376.0, -234468.0, 2027025.0,
+    // R[6]/n^6
+    -313076.0, 2027025.0,
+];
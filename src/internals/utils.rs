@@ -30,3 +30,23 @@ pub (in crate) fn sum_fourier_fast(epsilon: f64, params: &[f64;18]) -> [f64;7] {
     ]
 }
 
+
+use crate::internals::constants::AUX_COEFF_SIZE;
+
+// Evaluate the coefficients of a series in the third flattening `n` from a
+// packed coefficient array, see `ALP_COEFF`. Entry 0 is unused.
+pub (in crate) fn aux_series(n: f64, params: &[f64; AUX_COEFF_SIZE]) -> [f64; 7] {
+    use crate::geomath::polyval;
+    use crate::internals::constants::GEODESIC_ORDER;
+
+    let mut c = [0.0_f64; 7];
+    let mut d = n;
+    let mut o = 0_usize;
+    for (l, val) in c.iter_mut().enumerate().skip(1) {
+        let m = GEODESIC_ORDER - l;
+        *val = d * polyval(m, &params[o..], n) / params[o + m + 1];
+        o += m + 2;
+        d *= n;
+    }
+    c
+}
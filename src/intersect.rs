@@ -0,0 +1,835 @@
+#![allow(non_snake_case)]
+
+use crate::geodesic_capability as caps;
+use crate::geodesic_line::GeodesicLine;
+use crate::geomath;
+use crate::{Geodesic, InverseGeodesic};
+
+const NUMIT: usize = 100;
+// The largest number of tiles, each about half a circumference wide, along
+// each side of the square searched by all
+const MAXTILES: f64 = 100.0;
+
+/// A tentative intersection, as displacements along each geodesic plus the
+/// coincidence indicator.
+#[derive(Debug, Clone, Copy)]
+struct XPoint {
+    x: f64,
+    y: f64,
+    c: i32,
+}
+
+impl XPoint {
+    fn new(x: f64, y: f64) -> Self {
+        XPoint { x, y, c: 0 }
+    }
+
+    // The L1 distance, which is what "closest" is measured with
+    fn dist(&self, p: XPoint) -> f64 {
+        (self.x - p.x).abs() + (self.y - p.y).abs()
+    }
+
+    fn add(&self, p: XPoint) -> Self {
+        XPoint {
+            x: self.x + p.x,
+            y: self.y + p.y,
+            c: if p.c != 0 { p.c } else { self.c },
+        }
+    }
+
+    fn data(&self) -> (f64, f64, i32) {
+        (self.x, self.y, self.c)
+    }
+}
+
+/// Find the intersections of two geodesics.
+///
+/// This follows C. F. F. Karney, [Geodesic intersections](https://doi.org/10.1080/00396265.2023.2217604),
+/// J. Surveying Eng. 150(3), 04024005 (2024).
+///
+/// Each geodesic is given as a starting point and an azimuth (or as a
+/// [`GeodesicLine`]), and an intersection is reported as the displacements
+/// `(x, y)` in meters from the starting points of the two geodesics, together
+/// with a coincidence indicator `c`:
+///  - `c == 0`, the geodesics cross at this point;
+///  - `c == 1`, the geodesics are coincident and run in the same direction;
+///  - `c == -1`, the geodesics are coincident and run in opposite directions.
+///
+/// Intersections are ranked by the L1 distance `|x - x0| + |y - y0|` from
+/// some reference displacement `(x0, y0)`, which is `(0, 0)` unless given.
+/// For coincident geodesics every point on the line `x - x0 = c * (y - y0)`
+/// is an intersection, and the one closest to the reference is returned.
+///
+/// The method is reliable for ellipsoids with `|f|` up to about 1/50.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, Intersect, DirectGeodesic};
+///
+/// let g = Geodesic::wgs84();
+/// let inter = Intersect::new(g);
+///
+/// // A flight heading east from JFK and one heading north from Miami
+/// let (x, y, c) = inter.closest(40.64, -73.78, 90.0, 25.79, -80.29, 0.0);
+/// assert_eq!(c, 0);
+///
+/// let (lat_x, lon_x): (f64, f64) = g.direct(40.64, -73.78, 90.0, x);
+/// let (lat_y, lon_y): (f64, f64) = g.direct(25.79, -80.29, 0.0, y);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(lat_x, lat_y, epsilon = 1e-9);
+/// assert_relative_eq!(lon_x, lon_y, epsilon = 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Intersect<'a> {
+    geod: &'a Geodesic,
+    // The authalic radius, used for the spherical approximation
+    r: f64,
+    // Half the circumference of the authalic sphere
+    d: f64,
+    eps: f64,
+    tol: f64,
+    delta: f64,
+    // Intersections closer than t1 to the reference are the closest
+    t1: f64,
+    // Offsets of the starting points for closest, next and all
+    d1: f64,
+    d2: f64,
+    d3: f64,
+}
+
+impl<'a> Intersect<'a> {
+    /// The capabilities a [`GeodesicLine`] needs to be passed to the
+    /// `*_lines` methods.
+    ///
+    /// Lines passed to [`segment_lines`](Self::segment_lines) must also have
+    /// their distance set, which [`Geodesic::inverse_line`] does.
+    pub const LINE_CAPS: u64 = caps::LATITUDE
+        | caps::LONGITUDE
+        | caps::AZIMUTH
+        | caps::REDUCEDLENGTH
+        | caps::GEODESICSCALE
+        | caps::DISTANCE_IN;
+
+    /// Create a new intersection solver using a Geodesic.
+    ///
+    /// # Panics
+    /// Panics if the ellipsoid is too eccentric for the starting points of
+    /// the searches to be chosen reliably, roughly if `f > 0.38` or
+    /// `f < -1/3`.
+    pub fn new(geod: &'a Geodesic) -> Self {
+        let a = geod.equatorial_radius();
+        let f = geod.flattening();
+        let r = (geod.area() / (4.0 * std::f64::consts::PI)).sqrt();
+        let d = r * std::f64::consts::PI;
+        let mut inter = Intersect {
+            geod,
+            r,
+            d,
+            eps: 3.0 * f64::EPSILON,
+            tol: d * f64::EPSILON.powf(3.0 / 4.0),
+            delta: d * f64::EPSILON.powf(1.0 / 5.0),
+            t1: 0.0,
+            d1: 0.0,
+            d2: 0.0,
+            d3: 0.0,
+        };
+        // These follow GeographicLib: t1 is the shortest distance between
+        // distinct intersections, t2 the distance between intersections of
+        // meridians, t3 the shortest distance to the next intersection of
+        // oblique geodesics and t4 the spacing of the tiles for all. For a
+        // prolate ellipsoid t3 is half the meridian (t5) and t1 and t2 swap.
+        let mut t1 = a * (1.0 - f) * std::f64::consts::PI;
+        let mut t2 = 2.0 * inter.distpolar(90.0);
+        let (t3, t4) = if f > 0.0 {
+            (inter.distoblique(), t1)
+        } else {
+            let t5: f64 = geod.inverse(0.0, 0.0, 90.0, 0.0);
+            std::mem::swap(&mut t1, &mut t2);
+            (2.0 * t5, inter.polarb())
+        };
+        inter.t1 = t1;
+        inter.d1 = t2 / 2.0;
+        inter.d2 = 2.0 * t3 / 3.0;
+        inter.d3 = t4 - inter.delta;
+        assert!(
+            inter.d1 < inter.d3 && inter.d2 < inter.d3 && inter.d2 < 2.0 * t1,
+            "Intersect::new: ellipsoid too eccentric, f = {}",
+            f
+        );
+        inter
+    }
+
+    /// Find the closest intersection of two geodesics, each given by a
+    /// starting point and an azimuth (degrees).
+    ///
+    /// Returns `(x, y, c)`, see [`Intersect`].
+    pub fn closest(
+        &self,
+        latX: f64,
+        lonX: f64,
+        aziX: f64,
+        latY: f64,
+        lonY: f64,
+        aziY: f64,
+    ) -> (f64, f64, i32) {
+        self.closest_lines(
+            &self.geod.line(latX, lonX, aziX, Self::LINE_CAPS),
+            &self.geod.line(latY, lonY, aziY, Self::LINE_CAPS),
+            (0.0, 0.0),
+        )
+    }
+
+    /// Find the intersection of two geodesic lines closest to the
+    /// displacements `p0`.
+    ///
+    /// The lines need [`LINE_CAPS`](Self::LINE_CAPS). Returns `(x, y, c)`, see [`Intersect`].
+    pub fn closest_lines(
+        &self,
+        lineX: &GeodesicLine,
+        lineY: &GeodesicLine,
+        p0: (f64, f64),
+    ) -> (f64, f64, i32) {
+        self.closest_int(lineX, lineY, XPoint::new(p0.0, p0.1)).data()
+    }
+
+    /// Find the intersection of two geodesic segments, each given by its
+    /// end points.
+    ///
+    /// Returns `(x, y, segmode, c)`. `segmode` is `kx + 3 * ky` where `kx` is
+    /// `-1`, `0` or `1` as `x` is before, within or beyond the first segment,
+    /// and likewise `ky` for `y` and the second segment. So `segmode == 0`
+    /// means the segments intersect. If they don't, the intersection closest
+    /// to the midpoints of the segments is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn segment(
+        &self,
+        latX1: f64,
+        lonX1: f64,
+        latX2: f64,
+        lonX2: f64,
+        latY1: f64,
+        lonY1: f64,
+        latY2: f64,
+        lonY2: f64,
+    ) -> (f64, f64, i32, i32) {
+        self.segment_lines(
+            &self.geod.inverse_line(latX1, lonX1, latX2, lonX2, Self::LINE_CAPS),
+            &self.geod.inverse_line(latY1, lonY1, latY2, lonY2, Self::LINE_CAPS),
+        )
+    }
+
+    /// Find the intersection of two geodesic segments, given as lines with
+    /// their distance set.
+    ///
+    /// Returns `(x, y, segmode, c)`, see [`segment`](Self::segment).
+    pub fn segment_lines(&self, lineX: &GeodesicLine, lineY: &GeodesicLine) -> (f64, f64, i32, i32) {
+        let sx = lineX.distance();
+        let sy = lineY.distance();
+        let p0 = XPoint::new(sx / 2.0, sy / 2.0);
+        let q = self.closest_int(lineX, lineY, p0);
+        let q = fixsegment(sx, sy, q);
+        (q.x, q.y, segmentmode(sx, sy, q), q.c)
+    }
+
+    /// Find the next closest intersection of two geodesics which start at
+    /// the same point, with azimuths `aziX` and `aziY`.
+    ///
+    /// This is the intersection closest to the common starting point, other
+    /// than the starting point itself. If the geodesics are coincident, their
+    /// conjugate points are counted as intersections, being the limit of the
+    /// next intersection as the geodesics approach each other.
+    ///
+    /// Returns `(x, y, c)`, see [`Intersect`].
+    pub fn next(&self, latX: f64, lonX: f64, aziX: f64, aziY: f64) -> (f64, f64, i32) {
+        self.next_lines(
+            &self.geod.line(latX, lonX, aziX, Self::LINE_CAPS),
+            &self.geod.line(latX, lonX, aziY, Self::LINE_CAPS),
+        )
+    }
+
+    /// Find the next closest intersection of two geodesic lines which start
+    /// at the same point.
+    ///
+    /// The lines need [`LINE_CAPS`](Self::LINE_CAPS). Returns `(x, y, c)`, see [`Intersect`].
+    pub fn next_lines(&self, lineX: &GeodesicLine, lineY: &GeodesicLine) -> (f64, f64, i32) {
+        self.next_int(lineX, lineY).data()
+    }
+
+    /// Find all the intersections of two geodesics within an L1 distance
+    /// `maxdist` of the starting points.
+    ///
+    /// The intersections are sorted by their distance from the starting
+    /// points. Each is `(x, y, c)`, see [`Intersect`].
+    ///
+    /// # Panics
+    /// Panics if `maxdist` is negative or NaN, or larger than about 100 times
+    /// half the circumference of the ellipsoid (2&times;10<sup>9</sup> m for
+    /// WGS84), since the cost grows as the square of `maxdist`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn all(
+        &self,
+        latX: f64,
+        lonX: f64,
+        aziX: f64,
+        latY: f64,
+        lonY: f64,
+        aziY: f64,
+        maxdist: f64,
+    ) -> Vec<(f64, f64, i32)> {
+        self.all_lines(
+            &self.geod.line(latX, lonX, aziX, Self::LINE_CAPS),
+            &self.geod.line(latY, lonY, aziY, Self::LINE_CAPS),
+            maxdist,
+            (0.0, 0.0),
+        )
+    }
+
+    /// Find all the intersections of two geodesic lines within an L1
+    /// distance `maxdist` of the displacements `p0`.
+    ///
+    /// The lines need [`LINE_CAPS`](Self::LINE_CAPS). The intersections are sorted by their
+    /// distance from `p0`. Each is `(x, y, c)`, see [`Intersect`].
+    ///
+    /// # Panics
+    /// Panics if `maxdist` is out of range, see [`all`](Self::all).
+    pub fn all_lines(
+        &self,
+        lineX: &GeodesicLine,
+        lineY: &GeodesicLine,
+        maxdist: f64,
+        p0: (f64, f64),
+    ) -> Vec<(f64, f64, i32)> {
+        let p0 = XPoint::new(p0.0, p0.1);
+        self.all_int(lineX, lineY, maxdist, p0)
+            .iter()
+            .map(XPoint::data)
+            .collect()
+    }
+
+    fn equal(&self, p: XPoint, q: XPoint) -> bool {
+        (p.x - q.x).abs() <= self.delta && (p.y - q.y).abs() <= self.delta
+    }
+
+    // One step of the solution, treating the triangle formed by the two
+    // current points and the intersection as spherical
+    fn spherical(&self, lineX: &GeodesicLine, lineY: &GeodesicLine, p: XPoint) -> XPoint {
+        let mask = caps::LATITUDE | caps::LONGITUDE | caps::AZIMUTH;
+        let (_, latX, lonX, aziX, _, _, _, _, _) = lineX._gen_position(false, p.x, mask);
+        let (_, latY, lonY, aziY, _, _, _, _, _) = lineY._gen_position(false, p.y, mask);
+        let (z, aziXa, aziYa, _a12): (f64, f64, f64, f64) = self.geod.inverse(latX, lonX, latY, lonY);
+        let (sinz, cosz) = (z / self.r).sin_cos();
+        // X = interior angle at X, Y = exterior angle at Y
+        let (X, dX) = geomath::ang_diff(aziX, aziXa);
+        let (Y, dY) = geomath::ang_diff(aziY, aziYa);
+        let (XY, dXY) = geomath::ang_diff(X, Y);
+        // The triangle may be inverted
+        let s = 1.0_f64.copysign(XY + (dXY + dY - dX));
+        let (sinX, cosX) = geomath::sincosde(s * X, s * dX);
+        let (sinY, cosY) = geomath::sincosde(s * Y, s * dY);
+        if z <= self.eps * self.r {
+            // Already at the intersection, check whether the lines are
+            // parallel or antiparallel
+            let c = if (sinX - sinY).abs() <= self.eps && (cosX - cosY).abs() <= self.eps {
+                1
+            } else if (sinX + sinY).abs() <= self.eps && (cosX + cosY).abs() <= self.eps {
+                -1
+            } else {
+                0
+            };
+            XPoint { x: 0.0, y: 0.0, c }
+        } else if sinX.abs() <= self.eps && sinY.abs() <= self.eps {
+            // Coincident geodesics, place the intersection at the midpoint
+            let c = if cosX * cosY > 0.0 { 1 } else { -1 };
+            XPoint {
+                x: cosX * z / 2.0,
+                y: -cosY * z / 2.0,
+                c,
+            }
+        } else {
+            // z may exceed half the circumference, so sinz < 0 is handled
+            // by using atan2
+            XPoint::new(
+                self.r * (sinY * sinz).atan2(sinY * cosX * cosz - cosY * sinX),
+                self.r * (sinX * sinz).atan2(-sinX * cosY * cosz + cosX * sinY),
+            )
+        }
+    }
+
+    // Iterate the spherical solution from p0 until it converges
+    fn basic(&self, lineX: &GeodesicLine, lineY: &GeodesicLine, p0: XPoint) -> XPoint {
+        let mut q = p0;
+        for _ in 0..NUMIT {
+            let dq = self.spherical(lineX, lineY, q);
+            q = q.add(dq);
+            let dist = dq.dist(XPoint::new(0.0, 0.0));
+            if q.c != 0 || dist <= self.tol || dist.is_nan() {
+                break;
+            }
+        }
+        q
+    }
+
+    fn closest_int(&self, lineX: &GeodesicLine, lineY: &GeodesicLine, p0: XPoint) -> XPoint {
+        const IX: [f64; 5] = [0.0, 1.0, -1.0, 0.0, 0.0];
+        const IY: [f64; 5] = [0.0, 0.0, 0.0, 1.0, -1.0];
+        let mut q = XPoint::new(f64::NAN, f64::NAN);
+        for n in 0..IX.len() {
+            let start = p0.add(XPoint::new(IX[n] * self.d1, IY[n] * self.d1));
+            let qx = fixcoincident(p0, self.basic(lineX, lineY, start));
+            if n == 0 || qx.dist(p0) < q.dist(p0) {
+                q = qx;
+            }
+            // Nothing else can be closer
+            if q.dist(p0) < self.t1 {
+                break;
+            }
+        }
+        q
+    }
+
+    fn next_int(&self, lineX: &GeodesicLine, lineY: &GeodesicLine) -> XPoint {
+        const IX: [f64; 8] = [-1.0, -1.0, 1.0, 1.0, -2.0, 0.0, 2.0, 0.0];
+        const IY: [f64; 8] = [-1.0, 1.0, -1.0, 1.0, 0.0, 2.0, 0.0, -2.0];
+        let z = XPoint::new(0.0, 0.0);
+        let mut q = XPoint::new(f64::INFINITY, 0.0);
+        for n in 0..IX.len() {
+            let start = XPoint::new(IX[n] * self.d2, IY[n] * self.d2);
+            let qx = fixcoincident(z, self.basic(lineX, lineY, start));
+            let zerop = self.equal(z, qx);
+            if qx.c == 0 && zerop {
+                continue;
+            }
+            if qx.c != 0 && zerop {
+                // Coincident geodesics, the limit of the next intersection
+                // of two nearly coincident geodesics is the conjugate point
+                for sgn in [-1.0, 1.0] {
+                    let s = self.conjugate_dist(lineX, sgn * self.d, false, 0.0, 1.0, 1.0);
+                    let qa = XPoint {
+                        x: s,
+                        y: f64::from(qx.c) * s,
+                        c: qx.c,
+                    };
+                    if qa.dist(z) < q.dist(z) {
+                        q = qa;
+                    }
+                }
+            } else if qx.dist(z) < q.dist(z) {
+                q = qx;
+            }
+        }
+        q
+    }
+
+    fn all_int(&self, lineX: &GeodesicLine, lineY: &GeodesicLine, maxdist: f64, p0: XPoint) -> Vec<XPoint> {
+        let maxdistx = maxdist + self.delta;
+        // Cover the L1 ball of radius maxdistx with an m x m set of tiles,
+        // rotated by 45 degrees, with a starting point at each center
+        let m = (maxdistx / self.d3).ceil();
+        assert!(
+            maxdist >= 0.0 && m <= MAXTILES,
+            "Intersect::all: maxdist {} not in [0, {}]",
+            maxdist,
+            MAXTILES * self.d3 - self.delta
+        );
+        let m = (m as i64).max(1);
+        let n = m - 1;
+        let d3 = maxdistx / m as f64;
+        let mut start = vec![p0];
+        for i in (-n..=n).step_by(2) {
+            for j in (-n..=n).step_by(2) {
+                if !(i == 0 && j == 0) {
+                    start.push(p0.add(XPoint::new(
+                        d3 * (i + j) as f64 / 2.0,
+                        d3 * (i - j) as f64 / 2.0,
+                    )));
+                }
+            }
+        }
+        let mut found: Vec<XPoint> = Vec::new();
+        // Set once coincident geodesics are detected
+        let mut c0 = 0;
+        for &s in start.iter() {
+            let q = self.basic(lineX, lineY, s);
+            if !(q.x.is_finite() && q.y.is_finite()) {
+                continue;
+            }
+            let q = fixcoincident(p0, q);
+            if found.iter().any(|&p| self.equal(p, q)) {
+                continue;
+            }
+            if q.c != 0 {
+                // Every point on this line is an intersection; keep the
+                // closest to p0 plus the line of conjugate points
+                c0 = q.c;
+                found.retain(|&p| !self.equal(fixcoincident_c(p0, p, c0), q));
+                let s0 = q.x;
+                let (_, _, _, _, _, m12, M12, M21, _) =
+                    lineX._gen_position(false, s0, caps::REDUCEDLENGTH | caps::GEODESICSCALE);
+                for sgn in [-1.0, 1.0] {
+                    let mut sa = 0.0;
+                    loop {
+                        sa = self.conjugate_dist(lineX, s0 + sa + sgn * self.d, false, m12, M12, M21) - s0;
+                        let qc = q.add(XPoint::new(sa, f64::from(c0) * sa));
+                        found.push(qc);
+                        if qc.dist(p0) > maxdistx || qc.x.is_nan() {
+                            break;
+                        }
+                    }
+                }
+            } else if c0 != 0
+                && found
+                    .iter()
+                    .any(|&p| p.c != 0 && self.equal(p, fixcoincident_c(p0, q, c0)))
+            {
+                // On a line of coincident intersections already found
+                continue;
+            }
+            found.push(q);
+        }
+        found.retain(|p| p.dist(p0) <= maxdist);
+        found.sort_by(|p, q| {
+            p.dist(p0)
+                .total_cmp(&q.dist(p0))
+                .then(p.x.total_cmp(&q.x))
+                .then(p.y.total_cmp(&q.y))
+        });
+        found
+    }
+
+    // Find the distance to the point 3 on the line where m23 = 0, i.e.
+    // where point 3 is conjugate to point 2, or where M23 = 0 if semi,
+    // starting at s3. Point 2 is given by its m12, M12 and M21 relative to
+    // the start of the line.
+    fn conjugate_dist(&self, line: &GeodesicLine, s3: f64, semi: bool, m12: f64, M12: f64, M21: f64) -> f64 {
+        let mut s = s3;
+        for _ in 0..NUMIT {
+            let (_, _, _, _, _, m13, M13, M31, _) =
+                line._gen_position(false, s, caps::REDUCEDLENGTH | caps::GEODESICSCALE);
+            // See "Algorithms for geodesics", eqs. 31, 32, 33
+            let m23 = m13 * M12 - m12 * M13;
+            let M23 = M13 * M21 + if m12 == 0.0 { 0.0 } else { (1.0 - M12 * M21) * m13 / m12 };
+            let M32 = M31 * M12 + if m13 == 0.0 { 0.0 } else { (1.0 - M13 * M31) * m12 / m13 };
+            // dm23/ds3 = M32 and dM23/ds3 = -(1 - M23 * M32) / m23
+            let ds = if semi { m23 * M23 / (1.0 - M23 * M32) } else { -m23 / M32 };
+            s += ds;
+            if ds.abs() <= self.tol || ds.is_nan() {
+                break;
+            }
+        }
+        s
+    }
+
+    // The distance from latitude lat1 along a meridian heading north to the
+    // point semi-conjugate to it
+    fn distpolar(&self, lat1: f64) -> f64 {
+        let line = self.geod.line(lat1, 0.0, 0.0, Self::LINE_CAPS);
+        let s0 = (1.0 + self.geod.flattening() / 2.0) * self.geod.equatorial_radius() * std::f64::consts::PI / 2.0;
+        self.conjugate_dist(&line, s0, true, 0.0, 1.0, 1.0)
+    }
+
+    // Twice the minimum of distpolar for a prolate ellipsoid, found with
+    // quadratic fits of distpolar(lat), which is smallest near lat = 64
+    fn polarb(&self) -> f64 {
+        if self.geod.flattening() == 0.0 {
+            return self.d;
+        }
+        let (mut lat0, mut lat1, mut lat2) = (63.0, 65.0, 64.0);
+        let (mut s0, mut s1, mut s2) = (self.distpolar(lat0), self.distpolar(lat1), self.distpolar(lat2));
+        let mut sx = s2;
+        for _ in 0..10 {
+            let den = (lat1 - lat0) * s2 + (lat0 - lat2) * s1 + (lat2 - lat1) * s0;
+            if den == 0.0 || den.is_nan() {
+                break;
+            }
+            let latn = (((lat1 - lat0) * (lat1 + lat0) * s2
+                + (lat0 - lat2) * (lat0 + lat2) * s1
+                + (lat2 - lat1) * (lat2 + lat1) * s0)
+                / (2.0 * den))
+                .clamp(0.0, 90.0);
+            let sn = self.distpolar(latn);
+            if sn < sx {
+                sx = sn;
+            }
+            if latn > lat2 {
+                lat0 = lat2;
+                s0 = s2;
+            } else {
+                lat1 = lat2;
+                s1 = s2;
+            }
+            lat2 = latn;
+            s2 = sn;
+        }
+        2.0 * sx
+    }
+
+    // The conjugate distance s along a geodesic leaving the equator with
+    // azimuth azi, and ds, the amount by which the distance between the two
+    // points where the geodesic crosses itself near s/2 and -3s/2 exceeds 2s
+    fn conjdist(&self, azi: f64) -> (f64, f64) {
+        let line = self.geod.line(0.0, 0.0, azi, Self::LINE_CAPS);
+        let s = self.conjugate_dist(&line, self.d, false, 0.0, 1.0, 1.0);
+        let p = self.basic(&line, &line, XPoint::new(s / 2.0, -3.0 * s / 2.0));
+        (s, p.x - p.y - 2.0 * s)
+    }
+
+    // The conjugate distance at the azimuth where ds from conjdist vanishes,
+    // found with the secant method. This is the shortest distance to the
+    // next intersection of oblique geodesics on an oblate ellipsoid.
+    fn distoblique(&self) -> f64 {
+        if self.geod.flattening() == 0.0 {
+            return self.d;
+        }
+        let (mut azi0, mut azi1) = (46.0, 44.0);
+        let (_, mut ds0) = self.conjdist(azi0);
+        let (mut sx, mut ds1) = self.conjdist(azi1);
+        let mut dsx = ds1.abs();
+        for _ in 0..10 {
+            if ds1 == ds0 {
+                break;
+            }
+            let azin = (azi0 * ds1 - azi1 * ds0) / (ds1 - ds0);
+            azi0 = azi1;
+            ds0 = ds1;
+            azi1 = azin;
+            let (sn, dsn) = self.conjdist(azi1);
+            ds1 = dsn;
+            if ds1.abs() < dsx {
+                sx = sn;
+                dsx = ds1.abs();
+            }
+            if ds1 == 0.0 {
+                break;
+            }
+        }
+        sx
+    }
+}
+
+// Move an intersection on coincident geodesics to the point on the line
+// closest to p0
+fn fixcoincident(p0: XPoint, p: XPoint) -> XPoint {
+    fixcoincident_c(p0, p, p.c)
+}
+
+fn fixcoincident_c(p0: XPoint, p: XPoint, c: i32) -> XPoint {
+    if c == 0 {
+        return p;
+    }
+    let c = f64::from(c);
+    let s = ((p0.x + c * p0.y) - (p.x + c * p.y)) / 2.0;
+    p.add(XPoint::new(s, c * s))
+}
+
+// Move an intersection on coincident geodesics to the middle of the part
+// of the line inside the rectangle [0, sx] x [0, sy], or into the middle of
+// the gap if the line misses it
+fn fixsegment(sx: f64, sy: f64, p: XPoint) -> XPoint {
+    if p.c == 0 {
+        return p;
+    }
+    let c = f64::from(p.c);
+    // The points on the line are p + (s, c * s)
+    let (xlo, xhi) = (-p.x, sx - p.x);
+    let (ylo, yhi) = if p.c > 0 {
+        (-p.y, sy - p.y)
+    } else {
+        (p.y - sy, p.y)
+    };
+    let s = (xlo.max(ylo) + xhi.min(yhi)) / 2.0;
+    p.add(XPoint::new(s, c * s))
+}
+
+fn segmentmode(sx: f64, sy: f64, p: XPoint) -> i32 {
+    let kx = if p.x < 0.0 { -1 } else if p.x <= sx { 0 } else { 1 };
+    let ky = if p.y < 0.0 { -1 } else if p.y <= sy { 0 } else { 1 };
+    kx + 3 * ky
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DirectGeodesic;
+    use approx::assert_relative_eq;
+
+    // The distance between the points reached along each geodesic
+    fn miss(geod: &Geodesic, X: (f64, f64, f64), Y: (f64, f64, f64), x: f64, y: f64) -> f64 {
+        let (latX, lonX): (f64, f64) = geod.direct(X.0, X.1, X.2, x);
+        let (latY, lonY): (f64, f64) = geod.direct(Y.0, Y.1, Y.2, y);
+        geod.inverse(latX, lonX, latY, lonY)
+    }
+
+    #[test]
+    fn test_closest() {
+        let geod = Geodesic::wgs84();
+        let inter = Intersect::new(geod);
+        let cases = [
+            ((40.64, -73.78, 90.0), (25.79, -80.29, 0.0)),
+            ((51.47, -0.45, 60.0), (1.36, 103.99, -40.0)),
+            ((-33.95, 151.18, 120.0), (-37.0, 174.8, -100.0)),
+            ((10.0, 20.0, 30.0), (-70.0, -100.0, 170.0)),
+        ];
+        for &(X, Y) in cases.iter() {
+            let (x, y, c) = inter.closest(X.0, X.1, X.2, Y.0, Y.1, Y.2);
+            assert_eq!(c, 0);
+            assert!(miss(geod, X, Y, x, y) < 1e-6);
+
+            // No other intersection is closer
+            let lineX = geod.line(X.0, X.1, X.2, Intersect::LINE_CAPS);
+            let lineY = geod.line(Y.0, Y.1, Y.2, Intersect::LINE_CAPS);
+            for i in -4..=4 {
+                for j in -4..=4 {
+                    let p0 = (f64::from(i) * 5e6, f64::from(j) * 5e6);
+                    let (xp, yp, _) = inter.closest_lines(&lineX, &lineY, p0);
+                    assert!(x.abs() + y.abs() <= xp.abs() + yp.abs() + 1e-6);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_closest_coincident() {
+        let geod = Geodesic::wgs84();
+        let inter = Intersect::new(geod);
+        let deg = geod.equatorial_radius().to_radians();
+
+        // Along the equator, 10 degrees apart
+        let (x, y, c) = inter.closest(0.0, 0.0, 90.0, 0.0, 10.0, 90.0);
+        assert_eq!(c, 1);
+        assert_relative_eq!(x, 5.0 * deg, epsilon = 1e-6);
+        assert_relative_eq!(y, -5.0 * deg, epsilon = 1e-6);
+
+        let (x, y, c) = inter.closest(0.0, 0.0, 90.0, 0.0, 10.0, -90.0);
+        assert_eq!(c, -1);
+        assert_relative_eq!(x, 5.0 * deg, epsilon = 1e-6);
+        assert_relative_eq!(y, 5.0 * deg, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_segment() {
+        let geod = Geodesic::wgs84();
+        let inter = Intersect::new(geod);
+        let deg = geod.equatorial_radius().to_radians();
+
+        // The equator crossing a meridian
+        let (x, y, segmode, c) = inter.segment(0.0, -10.0, 0.0, 10.0, -10.0, 0.0, 10.0, 0.0);
+        assert_eq!((segmode, c), (0, 0));
+        assert_relative_eq!(x, 10.0 * deg, epsilon = 1e-6);
+        let s: f64 = geod.inverse(-10.0, 0.0, 0.0, 0.0);
+        assert_relative_eq!(y, s, epsilon = 1e-6);
+
+        // The second segment stops short of the equator
+        let (_, y, segmode, c) = inter.segment(0.0, -10.0, 0.0, 10.0, 5.0, 0.0, 10.0, 0.0);
+        assert_eq!((segmode, c), (-3, 0));
+        assert!(y < 0.0);
+
+        // Overlapping segments on the equator meet in the middle of the
+        // overlap
+        let (x, y, segmode, c) = inter.segment(0.0, -10.0, 0.0, 10.0, 0.0, 5.0, 0.0, 20.0);
+        assert_eq!((segmode, c), (0, 1));
+        assert_relative_eq!(x, 17.5 * deg, epsilon = 1e-6);
+        assert_relative_eq!(y, 2.5 * deg, epsilon = 1e-6);
+
+        // or the middle of the gap between them
+        let (x, y, segmode, c) = inter.segment(0.0, -10.0, 0.0, 10.0, 0.0, 30.0, 0.0, 20.0);
+        assert_eq!((segmode, c), (4, -1));
+        assert_relative_eq!(x, 25.0 * deg, epsilon = 1e-6);
+        assert_relative_eq!(y, 15.0 * deg, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_next() {
+        let geod = Geodesic::wgs84();
+        let inter = Intersect::new(geod);
+
+        let X = (10.0, 20.0, 30.0);
+        let Y = (10.0, 20.0, 100.0);
+        let (x, y, c) = inter.next(X.0, X.1, X.2, Y.2);
+        assert_eq!(c, 0);
+        assert!(x.abs() + y.abs() > 1e7);
+        assert!(miss(geod, X, Y, x, y) < 1e-6);
+
+        // Coincident geodesics give the conjugate point, unless the geodesic
+        // crosses itself first; the equator doesn't
+        let (x, y, c) = inter.next(0.0, 20.0, 90.0, 90.0);
+        assert_eq!(c, 1);
+        assert_eq!(x, y);
+        let line = geod.line(0.0, 20.0, 90.0, Intersect::LINE_CAPS);
+        let (_, _, _, _, _, m12, _, _, _) = line._gen_position(false, x, caps::REDUCEDLENGTH);
+        assert!(m12.abs() < 1e-6);
+
+        let (x, y, c) = inter.next(0.0, 20.0, 90.0, -90.0);
+        assert_eq!(c, -1);
+        assert_eq!(x, -y);
+    }
+
+    #[test]
+    fn test_all() {
+        let geod = Geodesic::wgs84();
+        let inter = Intersect::new(geod);
+
+        let X = (51.47, -0.45, 60.0);
+        let Y = (1.36, 103.99, -40.0);
+        let maxdist = 1e8;
+        let all = inter.all(X.0, X.1, X.2, Y.0, Y.1, Y.2, maxdist);
+        assert!(all.len() > 2);
+        assert_eq!(all[0], inter.closest(X.0, X.1, X.2, Y.0, Y.1, Y.2));
+        for w in all.windows(2) {
+            assert!(w[0].0.abs() + w[0].1.abs() <= w[1].0.abs() + w[1].1.abs());
+        }
+        for &(x, y, c) in all.iter() {
+            assert_eq!(c, 0);
+            assert!(x.abs() + y.abs() <= maxdist);
+            assert!(miss(geod, X, Y, x, y) < 1e-6);
+        }
+
+        assert!(inter.all(X.0, X.1, X.2, Y.0, Y.1, Y.2, 1e5).is_empty());
+    }
+
+    #[test]
+    fn test_eccentric() {
+        // The closest intersection is the first of all the intersections,
+        // even for quite eccentric ellipsoids
+        for &f in [1.0 / 3.0, -0.3].iter() {
+            let geod = Geodesic::new(6.4e6, f);
+            let inter = Intersect::new(&geod);
+            for i in 0..10 {
+                let t = f64::from(i);
+                let X = (80.0 - 17.0 * t, 37.0 * t - 170.0, 53.0 * t + 10.0);
+                let Y = (17.0 * t - 75.0, 160.0 - 41.0 * t, 200.0 - 67.0 * t);
+                let (x, y, c) = inter.closest(X.0, X.1, X.2, Y.0, Y.1, Y.2);
+                let all = inter.all(X.0, X.1, X.2, Y.0, Y.1, Y.2, 6e7);
+                assert_eq!(c, 0);
+                assert!(miss(&geod, X, Y, x, y) < 1e-6);
+                assert!(x.abs() + y.abs() <= all[0].0.abs() + all[0].1.abs() + 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "too eccentric")]
+    fn test_too_eccentric() {
+        Intersect::new(&Geodesic::new(6.4e6, 0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "too eccentric")]
+    fn test_too_eccentric_prolate() {
+        Intersect::new(&Geodesic::new(6.4e6, -0.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "maxdist")]
+    fn test_all_infinite() {
+        let inter = Intersect::new(Geodesic::wgs84());
+        inter.all(0.0, 0.0, 45.0, 0.0, 10.0, -45.0, f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "maxdist")]
+    fn test_all_nan() {
+        let inter = Intersect::new(Geodesic::wgs84());
+        inter.all(0.0, 0.0, 45.0, 0.0, 10.0, -45.0, f64::NAN);
+    }
+}
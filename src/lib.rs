@@ -66,6 +66,8 @@
 //! # Features
 //!
 //! 1. `accurate`: Enabled by default. Use the [`accurate`](https://docs.rs/accurate/latest/accurate/) crate to provide high accuracy polygon areas and perimeters in `PolygonArea`. Can be disabled for better performance or when `PolygonArea` is not being used.
+//! 2. `geo-types`: Adds the [`geometry`] module, with geodesic distances, lengths and areas of [`geo-types`](https://docs.rs/geo-types) geometries.
+//! 3. `serde`: Implements `Serialize` and `Deserialize` for `Geodesic`, `Winding`, `DirectResult` and `InverseResult`, and `Serialize` for [`PolygonArea`], which is restored with `PolygonArea::deserialize_state`.
 
 // Since this library is a port of an existing (cpp) codebase, there are times we opt
 // to follow the upstream implementation rather than follow rust idioms.
@@ -74,21 +76,45 @@
 #![allow(clippy::excessive_precision)]
 
 mod geodesic;
-pub use geodesic::{DirectGeodesic, Geodesic, InverseGeodesic};
+pub use geodesic::{ArcDirectGeodesic, DirectGeodesic, DirectResult, Geodesic, InverseGeodesic, InverseResult};
 
 pub mod geodesic_capability;
 pub use geodesic_capability as capability;
 
-
 mod geodesic_line;
+pub use geodesic_line::{GeodesicLine, LineArcPosition, LinePosition, Waypoints};
+
 mod geomath;
+mod intersect;
+pub use intersect::Intersect;
 mod polygon_area;
-pub use polygon_area::PolygonArea;
+pub use polygon_area::{MultiPolygonArea, PolygonArea, PolygonEdge, Polyline};
 pub use polygon_area::Winding;
 
+mod azimuthal_equidistant;
+pub use azimuthal_equidistant::AzimuthalEquidistant;
+mod cassini_soldner;
+pub use cassini_soldner::CassiniSoldner;
+mod gnomonic;
+pub use gnomonic::Gnomonic;
+mod transverse_mercator;
+pub use transverse_mercator::TransverseMercator;
+mod transverse_mercator_exact;
+pub use transverse_mercator_exact::TransverseMercatorExact;
+mod polar_stereographic;
+pub use polar_stereographic::PolarStereographic;
+pub mod utmups;
+pub mod mgrs;
+
+mod rhumb;
+pub use rhumb::{Rhumb, RhumbLine};
+
+#[cfg(feature = "geo-types")]
+pub mod geometry;
+
 mod internals;
 mod cached_weights;
-mod traits;
+pub mod traits;
 
-pub(in crate) mod problem;
+pub mod problem;
 pub(in crate) mod test_data;
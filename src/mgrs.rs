@@ -0,0 +1,624 @@
+//! Conversions between UTM/UPS coordinates and Military Grid Reference
+//! System (MGRS) strings.
+//!
+//! An MGRS string consists of the UTM zone number (omitted for UPS), a
+//! latitude band letter, two letters giving the 100 km square, and an even
+//! number of digits giving the easting and northing within the square. The
+//! precision `prec` is the number of digits of each of the easting and the
+//! northing: 0 gives a 100 km square, 5 gives 1 m, and the maximum, 11, gives
+//! 1 &mu;m. A precision of -1 gives just the grid zone, e.g. "38S" or "Z".
+//!
+//! MGRS references denote squares, and are formed by truncating, rather than
+//! rounding, the coordinates. When decoding, the center of the square is
+//! returned unless `centerp` is false. See
+//! [`utmups`](crate::utmups) for the UTM and UPS coordinates themselves.
+//!
+//! # Example
+//! ```rust
+//! use geographiclib_rs::mgrs;
+//!
+//! // Null Island, at the intersection of the equator and the prime meridian
+//! assert_eq!(mgrs::from_lat_lon(0.0, 0.0, 5).unwrap(), "31NAA6602100000");
+//! assert_eq!(mgrs::from_lat_lon(0.0, 0.0, 2).unwrap(), "31NAA6600");
+//! assert_eq!(mgrs::from_lat_lon(90.0, 0.0, 5).unwrap(), "ZAH0000000000");
+//!
+//! // The center of the 1 m square
+//! let (lat, lon, prec) = mgrs::to_lat_lon("31NAA6602100000").unwrap();
+//! assert_eq!(prec, 5);
+//!
+//! use approx::assert_relative_eq;
+//! assert_relative_eq!(lat, 0.0, epsilon = 1e-5);
+//! assert_relative_eq!(lon, 0.0, epsilon = 1e-5);
+//! ```
+
+use std::fmt;
+
+use crate::utmups::{self, latitude_band, UtmUpsError, ZoneSpec, MAX_UTM_ZONE, MIN_UTM_ZONE, TILE, UPS};
+
+/// The maximum precision, 11 digits for a resolution of 1 &mu;m.
+pub const MAX_PRECISION: i32 = 11;
+
+const BASE: i64 = 10;
+// The number of units per meter at the maximum precision
+const MULT: f64 = 1e6;
+const UTM_ROW_PERIOD: i32 = 20;
+const UTM_EVEN_ROW_SHIFT: i32 = 5;
+// The limits of the UTM and UPS coordinates in units of TILE
+const MIN_UTM_COL: i32 = 1;
+const MAX_UTM_COL: i32 = 9;
+const MIN_UTM_S_ROW: i32 = 10;
+const MAX_UTM_S_ROW: i32 = 100;
+const MIN_UTM_N_ROW: i32 = 0;
+const MAX_UTM_N_ROW: i32 = 95;
+const MIN_UPS_S_IND: i32 = 8;
+const MAX_UPS_S_IND: i32 = 32;
+const MIN_UPS_N_IND: i32 = 13;
+const MAX_UPS_N_IND: i32 = 27;
+const UPS_EASTING: i32 = 20;
+// The shift of the northings in the southern hemisphere (meters)
+const UTM_N_SHIFT: f64 = (MAX_UTM_S_ROW - MIN_UTM_N_ROW) as f64 * TILE;
+
+const LAT_BAND: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+const UPS_BAND: &[u8] = b"ABYZ";
+const UTM_COLS: [&[u8]; 3] = [b"ABCDEFGH", b"JKLMNPQR", b"STUVWXYZ"];
+const UTM_ROW: &[u8] = b"ABCDEFGHJKLMNPQRSTUV";
+const UPS_COLS: [&[u8]; 4] = [b"JKLPQRSTUXYZ", b"ABCFGHJKLPQR", b"RSTUXYZ", b"ABCFGHJ"];
+const UPS_ROWS: [&[u8]; 2] = [b"ABCDEFGHJKLMNPQRSTUVWXYZ", b"ABCDEFGHJKLMNP"];
+
+// The limits of the MGRS coordinates in units of TILE, indexed by
+// (utm ? 2 : 0) + (northp ? 1 : 0). The northings of the UTM zones are
+// extended across the equator.
+const MIN_EASTING: [i32; 4] = [MIN_UPS_S_IND, MIN_UPS_N_IND, MIN_UTM_COL, MIN_UTM_COL];
+const MAX_EASTING: [i32; 4] = [MAX_UPS_S_IND, MAX_UPS_N_IND, MAX_UTM_COL, MAX_UTM_COL];
+const MIN_NORTHING: [i32; 4] = [
+    MIN_UPS_S_IND,
+    MIN_UPS_N_IND,
+    MIN_UTM_S_ROW,
+    MIN_UTM_S_ROW - (MAX_UTM_S_ROW - MIN_UTM_N_ROW),
+];
+const MAX_NORTHING: [i32; 4] = [
+    MAX_UPS_S_IND,
+    MAX_UPS_N_IND,
+    MAX_UTM_N_ROW + (MAX_UTM_S_ROW - MIN_UTM_N_ROW),
+    MAX_UTM_N_ROW,
+];
+
+/// The errors from MGRS conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MgrsError {
+    /// The UTM/UPS coordinates are invalid or out of range.
+    UtmUps(UtmUpsError),
+    /// The precision is not in [-1, 11].
+    InvalidPrecision(i32),
+    /// The latitude is inconsistent with the UTM coordinates.
+    InconsistentLatitude(f64),
+    /// The MGRS string could not be parsed.
+    Parse { mgrs: String, reason: String },
+}
+
+impl fmt::Display for MgrsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MgrsError::UtmUps(err) => err.fmt(f),
+            MgrsError::InvalidPrecision(prec) => write!(f, "MGRS precision {prec} not in [-1, {MAX_PRECISION}]"),
+            MgrsError::InconsistentLatitude(lat) => {
+                write!(f, "latitude {lat} is inconsistent with UTM coordinates")
+            }
+            MgrsError::Parse { mgrs, reason } => write!(f, "invalid MGRS string {mgrs:?}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for MgrsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MgrsError::UtmUps(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<UtmUpsError> for MgrsError {
+    fn from(err: UtmUpsError) -> Self {
+        MgrsError::UtmUps(err)
+    }
+}
+
+/// The MGRS string with precision `prec` for (`lat`, `lon`), projected into
+/// the standard UTM or UPS zone.
+pub fn from_lat_lon(lat: f64, lon: f64, prec: i32) -> Result<String, MgrsError> {
+    let (zone, northp, x, y, _, _) = utmups::forward_checked(lat, lon, ZoneSpec::Standard, true)?;
+    forward_with_latitude(zone, northp, x, y, lat, prec)
+}
+
+/// The position of the center of the square denoted by an MGRS string.
+///
+/// # Returns
+/// - lat latitude of point (degrees).
+/// - lon longitude of point (degrees).
+/// - prec precision of the MGRS string, see [`reverse`].
+pub fn to_lat_lon(mgrs: &str) -> Result<(f64, f64, i32), MgrsError> {
+    let (zone, northp, x, y, prec) = reverse(mgrs, true)?;
+    let (lat, lon, _, _) = utmups::reverse_checked(zone, northp, x, y, true)?;
+    Ok((lat, lon, prec))
+}
+
+/// The MGRS string with precision `prec` for UTM or UPS coordinates, as
+/// given by [`utmups::forward`].
+pub fn forward(zone: i32, northp: bool, x: f64, y: f64, prec: i32) -> Result<String, MgrsError> {
+    let lat = if zone != UPS {
+        // Does a rough estimate of the latitude determine the latitude band?
+        let ys = (if northp { y } else { y - UTM_N_SHIFT }) / TILE;
+        if ys.abs() < 1.0 {
+            // Accurate enough near the equator
+            0.9 * ys
+        } else {
+            // The poleward bound is a fit from above of lat(x, y) for
+            // x = 500 km and y = [0 km, 950 km]
+            let latp = 0.901 * ys + 0.135f64.copysign(ys);
+            // The equatorward bound is a fit from below of lat(x, y) for
+            // x = 900 km and y = [0 km, 950 km]
+            let late = 0.902 * ys * (1.0 - 1.85e-6 * ys * ys);
+            if latitude_band(latp) == latitude_band(late) {
+                latp
+            } else {
+                // The bounds straddle a band boundary so compute the
+                // latitude accurately
+                utmups::reverse(zone, northp, x, y)?.0
+            }
+        }
+    } else {
+        // The latitude isn't needed for UPS
+        0.0
+    };
+    forward_with_latitude(zone, northp, x, y, lat, prec)
+}
+
+/// The MGRS string with precision `prec` for UTM or UPS coordinates, with
+/// `lat` the latitude of the point, which determines the latitude band. This
+/// avoids the cost of finding the latitude when it is already known.
+pub fn forward_with_latitude(
+    zone: i32,
+    northp: bool,
+    x: f64,
+    y: f64,
+    lat: f64,
+    prec: i32,
+) -> Result<String, MgrsError> {
+    // The smallest angle s.t. 90 - ANGEPS < 90 (approx 50e-12 arcsec)
+    const ANGEPS: f64 = 1.0 / (1u64 << 46) as f64;
+    if !(UPS..=MAX_UTM_ZONE).contains(&zone) {
+        return Err(UtmUpsError::InvalidZone(zone).into());
+    }
+    if !(-1..=MAX_PRECISION).contains(&prec) {
+        return Err(MgrsError::InvalidPrecision(prec));
+    }
+    if x.is_nan() || y.is_nan() || lat.is_nan() {
+        return Err(UtmUpsError::InvalidPosition { lat, lon: f64::NAN }.into());
+    }
+    let utmp = zone != UPS;
+    let (northp, x, y) = check_coords(utmp, northp, x, y)?;
+    let mut mgrs = String::with_capacity(5 + 2 * MAX_PRECISION as usize);
+    if utmp {
+        mgrs.push_str(&format!("{zone:02}"));
+    }
+    let ix = (x * MULT).floor() as i64;
+    let iy = (y * MULT).floor() as i64;
+    let m = (MULT * TILE) as i64;
+    let xh = (ix / m) as i32;
+    let yh = (iy / m) as i32;
+    let (band, col, row) = if utmp {
+        let zone1 = zone - 1;
+        // Correct fuzziness in latitude near the equator
+        let iband = if lat.abs() < ANGEPS {
+            if northp { 0 } else { -1 }
+        } else {
+            latitude_band(lat)
+        };
+        let icol = xh - MIN_UTM_COL;
+        let irow = utm_row(iband, icol, yh % UTM_ROW_PERIOD);
+        if irow != yh - if northp { MIN_UTM_N_ROW } else { MAX_UTM_S_ROW } {
+            return Err(MgrsError::InconsistentLatitude(lat));
+        }
+        let shift = if zone1 % 2 == 1 { UTM_EVEN_ROW_SHIFT } else { 0 };
+        (
+            LAT_BAND[(10 + iband) as usize],
+            UTM_COLS[(zone1 % 3) as usize][icol as usize],
+            UTM_ROW[((yh + shift) % UTM_ROW_PERIOD) as usize],
+        )
+    } else {
+        let eastp = xh >= UPS_EASTING;
+        let iband = (if northp { 2 } else { 0 }) + (if eastp { 1 } else { 0 });
+        let minind = if northp { MIN_UPS_N_IND } else { MIN_UPS_S_IND };
+        let icol = xh - if eastp { UPS_EASTING } else { minind };
+        (
+            UPS_BAND[iband],
+            UPS_COLS[iband][icol as usize],
+            UPS_ROWS[northp as usize][(yh - minind) as usize],
+        )
+    };
+    mgrs.push(band as char);
+    if prec < 0 {
+        return Ok(mgrs);
+    }
+    mgrs.push(col as char);
+    mgrs.push(row as char);
+    if prec > 0 {
+        let d = BASE.pow((MAX_PRECISION - prec) as u32);
+        let ix = (ix - m * xh as i64) / d;
+        let iy = (iy - m * yh as i64) / d;
+        let width = prec as usize;
+        mgrs.push_str(&format!("{ix:0width$}{iy:0width$}"));
+    }
+    Ok(mgrs)
+}
+
+/// Parse an MGRS string. The letters are case insensitive.
+///
+/// The position is the center of the square if `centerp` is true, otherwise
+/// its south west corner. For a grid zone only, a representative point in
+/// the grid zone is returned regardless of `centerp`.
+///
+/// # Returns
+/// - zone 0 for UPS or 1 through 60 for UTM.
+/// - northp true for the northern hemisphere.
+/// - x easting of point (meters).
+/// - y northing of point (meters).
+/// - prec precision of the MGRS string, -1 for a grid zone only.
+pub fn reverse(mgrs: &str, centerp: bool) -> Result<(i32, bool, f64, f64, i32), MgrsError> {
+    let err = |reason: String| MgrsError::Parse {
+        mgrs: mgrs.to_string(),
+        reason,
+    };
+    let s = mgrs.as_bytes();
+    let len = s.len();
+    let mut p = 0;
+    let mut zone = 0;
+    while p < len && s[p].is_ascii_digit() {
+        zone = 10 * zone + (s[p] - b'0') as i32;
+        p += 1;
+        if p > 2 {
+            return Err(err("more than 2 digits at the start".to_string()));
+        }
+    }
+    if p > 0 && !(MIN_UTM_ZONE..=MAX_UTM_ZONE).contains(&zone) {
+        return Err(UtmUpsError::InvalidZone(zone).into());
+    }
+    if p == len {
+        return Err(err("missing band letter".to_string()));
+    }
+    let utmp = zone != UPS;
+    let zonem1 = zone - 1;
+    let bands = if utmp { LAT_BAND } else { UPS_BAND };
+    let iband = lookup(bands, s[p])
+        .ok_or_else(|| err(format!("band letter {} not in {}", s[p] as char, if utmp { "UTM" } else { "UPS" })))?
+        as i32;
+    p += 1;
+    let northp = iband >= if utmp { 10 } else { 2 };
+    if p == len {
+        // Grid zone only, ignore centerp. Approximate length of a degree of
+        // meridian arc in units of TILE.
+        let deg = UTM_N_SHIFT / (90.0 * TILE);
+        let (x, y) = if utmp {
+            // Pick the central meridian except for 31V
+            let x = if zone == 31 && iband == 17 { 4.0 } else { 5.0 } * TILE;
+            // Pick the center of 8 degree latitude bands
+            let y = (8.0 * (iband as f64 - 9.5) * deg + 0.5).floor() * TILE + if northp { 0.0 } else { UTM_N_SHIFT };
+            (x, y)
+        } else {
+            // Pick a point at lat 86N or 86S on lon 90E or 90W
+            let sign = if iband % 2 == 1 { 1.0 } else { -1.0 };
+            let x = (sign * (4.0 * deg + 0.5).floor() + UPS_EASTING as f64) * TILE;
+            (x, UPS_EASTING as f64 * TILE)
+        };
+        return Ok((zone, northp, x, y, -1));
+    } else if len - p < 2 {
+        return Err(err("missing row letter".to_string()));
+    }
+    let cols = if utmp {
+        UTM_COLS[(zonem1 % 3) as usize]
+    } else {
+        UPS_COLS[iband as usize]
+    };
+    let rows = if utmp { UTM_ROW } else { UPS_ROWS[northp as usize] };
+    let mut icol =
+        lookup(cols, s[p]).ok_or_else(|| err(format!("column letter {} not in grid zone", s[p] as char)))? as i32;
+    p += 1;
+    let mut irow =
+        lookup(rows, s[p]).ok_or_else(|| err(format!("row letter {} not in grid zone", s[p] as char)))? as i32;
+    p += 1;
+    if utmp {
+        if zonem1 % 2 == 1 {
+            irow = (irow + UTM_ROW_PERIOD - UTM_EVEN_ROW_SHIFT) % UTM_ROW_PERIOD;
+        }
+        irow = utm_row(iband - 10, icol, irow);
+        if irow == MAX_UTM_S_ROW {
+            return Err(err(format!("block {} not in grid zone", &mgrs[p - 2..p])));
+        }
+        irow = if northp { irow } else { irow + 100 };
+        icol += MIN_UTM_COL;
+    } else {
+        let eastp = iband % 2 == 1;
+        let minind = if northp { MIN_UPS_N_IND } else { MIN_UPS_S_IND };
+        icol += if eastp { UPS_EASTING } else { minind };
+        irow += minind;
+    }
+    let digits = &s[p..];
+    if !digits.iter().all(u8::is_ascii_digit) {
+        return Err(err("non-digit in the easting and northing".to_string()));
+    }
+    if digits.len() % 2 == 1 {
+        return Err(err("odd number of digits".to_string()));
+    }
+    let prec = (digits.len() / 2) as i32;
+    if prec > MAX_PRECISION {
+        return Err(err(format!("more than {} digits", 2 * MAX_PRECISION)));
+    }
+    let (mut unit, mut x1, mut y1) = (1.0, icol as f64, irow as f64);
+    let (xdigits, ydigits) = digits.split_at(prec as usize);
+    for (dx, dy) in xdigits.iter().zip(ydigits) {
+        unit *= BASE as f64;
+        x1 = BASE as f64 * x1 + (dx - b'0') as f64;
+        y1 = BASE as f64 * y1 + (dy - b'0') as f64;
+    }
+    if centerp {
+        unit *= 2.0;
+        x1 = 2.0 * x1 + 1.0;
+        y1 = 2.0 * y1 + 1.0;
+    }
+    Ok((zone, northp, TILE * x1 / unit, TILE * y1 / unit, prec))
+}
+
+// The index of an upper or lower case letter in a list of upper case letters
+fn lookup(set: &[u8], c: u8) -> Option<usize> {
+    let c = c.to_ascii_uppercase();
+    set.iter().position(|&s| s == c)
+}
+
+// Check that the coordinates are within the MGRS limits. The limits are
+// closed on the lower end and open on the upper end, but a coordinate on the
+// upper end (e.g. after rounding) is shifted down by eps. This also folds
+// UTM northings to the correct hemisphere.
+fn check_coords(utmp: bool, northp: bool, x: f64, y: f64) -> Result<(bool, f64, f64), MgrsError> {
+    // The smallest length s.t. 1.0e7 - EPS < 1.0e7 (approx 1.9 nm). 25 =
+    // ceil(log_2(2e7)), using the half circumference because a northing of
+    // 195e5 is legal in the "southern" hemisphere.
+    const EPS: f64 = 1.0 / (1u64 << 28) as f64;
+    let (mut northp, mut x, mut y) = (northp, x, y);
+    let ix = (x / TILE).floor() as i32;
+    let iy = (y / TILE).floor() as i32;
+    let ind = (if utmp { 2 } else { 0 }) + (if northp { 1 } else { 0 });
+    if !(ix >= MIN_EASTING[ind] && ix < MAX_EASTING[ind]) {
+        if ix == MAX_EASTING[ind] && x == MAX_EASTING[ind] as f64 * TILE {
+            x -= EPS;
+        } else {
+            let (min, max) = (MIN_EASTING[ind] as f64 * TILE, MAX_EASTING[ind] as f64 * TILE);
+            return Err(UtmUpsError::EastingOutOfRange { x, min, max }.into());
+        }
+    }
+    if !(iy >= MIN_NORTHING[ind] && iy < MAX_NORTHING[ind]) {
+        if iy == MAX_NORTHING[ind] && y == MAX_NORTHING[ind] as f64 * TILE {
+            y -= EPS;
+        } else {
+            let (min, max) = (MIN_NORTHING[ind] as f64 * TILE, MAX_NORTHING[ind] as f64 * TILE);
+            return Err(UtmUpsError::NorthingOutOfRange { y, min, max }.into());
+        }
+    }
+    // Correct the UTM northing and hemisphere if necessary
+    if utmp {
+        if northp && iy < MIN_UTM_N_ROW {
+            northp = false;
+            y += UTM_N_SHIFT;
+        } else if !northp && iy >= MAX_UTM_S_ROW {
+            if y == MAX_UTM_S_ROW as f64 * TILE {
+                // If on the equator retain the southern hemisphere
+                y -= EPS;
+            } else {
+                northp = true;
+                y -= UTM_N_SHIFT;
+            }
+        }
+    }
+    Ok((northp, x, y))
+}
+
+// The true row index in [-90, 95) given the latitude band index iband in
+// [-10, 10), the column index icol in [0, 8) with origin of easting 100 km,
+// and the periodic row index irow in [0, 20) with origin the equator.
+// Returns MAX_UTM_S_ROW if irow and iband are incompatible.
+fn utm_row(iband: i32, icol: i32, irow: i32) -> i32 {
+    // Estimate the center row number for the latitude band,
+    // 90 deg = 100 tiles, 1 band = 8 deg = 100*8/90 tiles
+    let c = 100.0 * (8 * iband + 4) as f64 / 90.0;
+    let northp = if iband >= 0 { 1.0 } else { 0.0 };
+    // These are safe bounds on the rows
+    //  iband minrow maxrow
+    //   -10    -90    -81
+    //    -9    -80    -72
+    //    -8    -71    -63
+    //    -7    -63    -54
+    //    -6    -54    -45
+    //    -5    -45    -36
+    //    -4    -36    -27
+    //    -3    -27    -18
+    //    -2    -18     -9
+    //    -1     -9     -1
+    //     0      0      8
+    //     1      8     17
+    //     2     17     26
+    //     3     26     35
+    //     4     35     44
+    //     5     44     53
+    //     6     53     62
+    //     7     62     70
+    //     8     71     79
+    //     9     80     94
+    let minrow = if iband > -10 {
+        (c - 4.3 - 0.1 * northp).floor() as i32
+    } else {
+        -90
+    };
+    let maxrow = if iband < 9 {
+        (c + 4.4 - 0.1 * northp).floor() as i32
+    } else {
+        94
+    };
+    let baserow = (minrow + maxrow) / 2 - UTM_ROW_PERIOD / 2;
+    // Offset irow by the multiple of UTM_ROW_PERIOD which brings it as close
+    // as possible to the center of the latitude band, (minrow + maxrow) / 2.
+    // (Add MAX_UTM_S_ROW = 5 * UTM_ROW_PERIOD to ensure the operand is
+    // positive.)
+    let irow = (irow - baserow + MAX_UTM_S_ROW) % UTM_ROW_PERIOD + baserow;
+    if irow >= minrow && irow <= maxrow {
+        return irow;
+    }
+    // Outside the safe bounds, so need to check. Northings of 71e5 and 80e5
+    // intersect band boundaries
+    //   y = 71e5 in scol = 2 (x = [3e5,4e5] and x = [6e5,7e5])
+    //   y = 80e5 in scol = 1 (x = [2e5,3e5] and x = [7e5,8e5])
+    // This holds for all the ellipsoids given in NGA.SIG.0012_2.0.0_UTMUPS.
+    // Fold [-10,-1] -> [9,0]
+    let sband = if iband >= 0 { iband } else { -iband - 1 };
+    // Fold [-90,-1] -> [89,0]
+    let srow = if irow >= 0 { irow } else { -irow - 1 };
+    // Fold [4,7] -> [3,0]
+    let scol = if icol < 4 { icol } else { -icol + 7 };
+    // For example, the safe rows for band 8 are 71 - 79. However row 70 is
+    // allowed if scol = [2,3] and row 80 is allowed if scol = [0,1].
+    if (srow == 70 && sband == 8 && scol >= 2)
+        || (srow == 71 && sband == 7 && scol <= 2)
+        || (srow == 79 && sband == 9 && scol >= 1)
+        || (srow == 80 && sband == 8 && scol <= 1)
+    {
+        irow
+    } else {
+        MAX_UTM_S_ROW
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_from_lat_lon() {
+        // The Washington Monument
+        let strings: Vec<_> = [-1, 0, 1, 5, 11]
+            .iter()
+            .map(|&prec| from_lat_lon(38.8895, -77.0353, prec).unwrap())
+            .collect();
+        assert_eq!(strings, ["18S", "18SUJ", "18SUJ20", "18SUJ2347806483", "18SUJ2347806336906483241815"]);
+
+        assert_eq!(from_lat_lon(0.0, 0.0, 5).unwrap(), "31NAA6602100000");
+        // Just south of the equator is in band M
+        assert_eq!(from_lat_lon(-1e-9, 0.0, 0).unwrap(), "31MAV");
+        // The Norway and Svalbard exceptions
+        assert_eq!(from_lat_lon(60.0, 5.0, 5).unwrap(), "32VKM7697958157");
+        assert_eq!(from_lat_lon(78.0, 15.0, 5).unwrap(), "33XWG0000058369");
+        // The UPS regions
+        assert_eq!(from_lat_lon(90.0, 0.0, 5).unwrap(), "ZAH0000000000");
+        assert_eq!(from_lat_lon(-90.0, 0.0, 5).unwrap(), "BAN0000000000");
+        assert_eq!(from_lat_lon(85.0, -30.0, 5).unwrap(), "YXC2227118959");
+        assert_eq!(from_lat_lon(-85.0, 30.0, -1).unwrap(), "B");
+
+        assert_eq!(from_lat_lon(0.0, 0.0, 12), Err(MgrsError::InvalidPrecision(12)));
+        assert_eq!(from_lat_lon(0.0, 0.0, -2), Err(MgrsError::InvalidPrecision(-2)));
+        assert!(from_lat_lon(f64::NAN, 0.0, 5).is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let points = [
+            (38.8895, -77.0353),
+            (-33.8568, 151.2153),
+            (0.0, 0.0),
+            (-1e-9, 179.9),
+            (60.0, 5.0),
+            (78.0, 15.0),
+            (83.99, -100.0),
+            (-79.99, 60.0),
+            (85.0, -30.0),
+            (-85.0, 30.0),
+            (89.9, 123.0),
+        ];
+        for &(lat, lon) in points.iter() {
+            let (zone, northp, x, y, _, _) = utmups::forward(lat, lon).unwrap();
+            for prec in 0..=MAX_PRECISION {
+                let mgrs = from_lat_lon(lat, lon, prec).unwrap();
+                assert_eq!(mgrs.len(), if zone == UPS { 3 } else { 5 } + 2 * prec as usize);
+                // Without the latitude the same band is found
+                assert_eq!(forward(zone, northp, x, y, prec).unwrap(), mgrs);
+
+                // The point lies in the square
+                let size = TILE / 10f64.powi(prec);
+                let (zone1, northp1, x1, y1, prec1) = reverse(&mgrs, false).unwrap();
+                assert_eq!((zone1, northp1, prec1), (zone, northp, prec));
+                assert!(x1 <= x && x < x1 + size && y1 <= y && y < y1 + size);
+                let (_, _, x1, y1, _) = reverse(&mgrs, true).unwrap();
+                assert!((x1 - x).abs() <= size / 2.0 + 1e-9 && (y1 - y).abs() <= size / 2.0 + 1e-9);
+            }
+            let (lat1, lon1, prec) = to_lat_lon(&from_lat_lon(lat, lon, MAX_PRECISION).unwrap()).unwrap();
+            assert_eq!(prec, MAX_PRECISION);
+            // The center of a 1 um square
+            assert_relative_eq!(lat1, lat, epsilon = 1e-10);
+            if lat.abs() < 89.0 {
+                assert_relative_eq!(lon1, lon, epsilon = 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert_eq!(reverse("31NAA6602100000", false), Ok((31, true, 166021.0, 0.0, 5)));
+        assert_eq!(reverse("31naa6602100000", true), Ok((31, true, 166021.5, 0.5, 5)));
+        assert_eq!(reverse("5QKB", true), Ok((5, true, 250e3, 2150e3, 0)));
+        assert_eq!(reverse("ZAH", true), Ok((UPS, true, 2050e3, 2050e3, 0)));
+        // Grid zones only give representative points
+        let (zone, northp, x, _, prec) = reverse("31V", true).unwrap();
+        assert_eq!((zone, northp, x, prec), (31, true, 400e3, -1));
+        let (lat, lon, _) = to_lat_lon("31V").unwrap();
+        assert!(56.0 < lat && lat < 64.0 && 0.0 < lon && lon < 3.0);
+        let (lat, lon, _) = to_lat_lon("Y").unwrap();
+        assert_relative_eq!(lat, 86.0, epsilon = 0.5);
+        assert_relative_eq!(lon, -90.0, epsilon = 1e-12);
+
+        // Parse errors and band checks
+        for s in [
+            "", "31", "123NAA", "31IAA", "31NA", "31NIA", "31NAA123", "31NAA12x4", "31NAA123456789012345678901234", "K",
+            "CAA", "31NAL", "38SMV",
+        ]
+        .iter()
+        {
+            assert!(matches!(reverse(s, true), Err(MgrsError::Parse { .. })), "{}", s);
+        }
+        assert_eq!(reverse("61NAA", true), Err(MgrsError::UtmUps(UtmUpsError::InvalidZone(61))));
+    }
+
+    #[test]
+    fn test_limits() {
+        // Inconsistent latitude bands
+        let (zone, northp, x, y, _, _) = utmups::forward(38.8895, -77.0353).unwrap();
+        assert_eq!(
+            forward_with_latitude(zone, northp, x, y, 30.0, 5),
+            Err(MgrsError::InconsistentLatitude(30.0))
+        );
+        assert!(forward_with_latitude(zone, northp, x, y, 38.0, 5).is_ok());
+
+        // The MGRS limits are closed below and open above, but points on the
+        // upper limit are shifted down
+        assert_eq!(forward(31, true, 900e3, 0.0, 0).unwrap(), "31NHA");
+        assert!(matches!(
+            forward(31, true, 900e3 + 1.0, 0.0, 0),
+            Err(MgrsError::UtmUps(UtmUpsError::EastingOutOfRange { .. }))
+        ));
+        assert!(matches!(
+            forward(31, true, 500e3, 9.6e6, 0),
+            Err(MgrsError::UtmUps(UtmUpsError::NorthingOutOfRange { .. }))
+        ));
+        // UTM northings are folded across the equator
+        assert_eq!(forward(31, true, 500e3, -1.0, 0).unwrap(), "31MEV");
+        assert_eq!(forward(31, false, 500e3, 10e6, 0).unwrap(), "31MEV");
+        assert!(forward(61, true, 500e3, 0.0, 0).is_err());
+    }
+}
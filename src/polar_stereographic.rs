@@ -0,0 +1,280 @@
+use std::sync;
+
+use crate::geomath;
+use crate::internals::constants::{WGS84_A, WGS84_F};
+
+/// The central scale factor of the UPS projection.
+pub(in crate) const UPS_K0: f64 = 0.994;
+
+/// The polar stereographic projection.
+///
+/// This is the conformal projection of the ellipsoid onto a plane tangent
+/// at, or cutting the ellipsoid near, a pole, as used by the Universal Polar
+/// Stereographic (UPS) system and many Antarctic datasets. The projection is
+/// about the north pole or the south pole as selected by `northp` in
+/// `forward` and `reverse`. The pole is at the origin, the `y` axis of the
+/// north polar projection points along the meridian 180&deg; and that of the
+/// south polar projection along the meridian 0&deg;. False eastings and
+/// northings are not included.
+///
+/// See J. P. Snyder, [Map Projections: A Working Manual](https://pubs.usgs.gov/pp/1395/report.pdf),
+/// USGS Professional Paper 1395 (1987), pp. 160&ndash;163.
+#[derive(Debug, Clone)]
+pub struct PolarStereographic {
+    a: f64,
+    f: f64,
+    k0: f64,
+    e2: f64,
+    es: f64,
+    e2m: f64,
+    c: f64,
+}
+
+static UPS: sync::OnceLock<PolarStereographic> = sync::OnceLock::new();
+
+/// PolarStereographic maps between geographic coordinates and the polar
+/// stereographic projection.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::PolarStereographic;
+///
+/// // The standard parallel of the Antarctic Polar Stereographic projection
+/// // (EPSG:3031) is 71S
+/// let mut ps = PolarStereographic::new(6378137.0, 1.0 / 298.257223563, 1.0);
+/// ps.set_scale(-71.0, 1.0);
+///
+/// // McMurdo Station
+/// let (x, y, _gamma, k) = ps.forward(false, -77.846, 166.676);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x, 305433.40, epsilon = 0.01);
+/// assert_relative_eq!(y, -1289661.03, epsilon = 0.01);
+/// assert_relative_eq!(k, 0.98379, epsilon = 1e-5);
+/// let (lat, lon, _gamma, _k) = ps.reverse(false, x, y);
+/// assert_relative_eq!(lat, -77.846, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 166.676, epsilon = 1e-12);
+/// ```
+impl PolarStereographic {
+    /// Create a PolarStereographic projection for the ellipsoid with
+    /// equatorial radius `a` (meters) and flattening `f`, with central scale
+    /// factor `k0` at the pole.
+    ///
+    /// # Panics
+    /// Panics unless `a` and `k0` are positive and finite and `f` is less
+    /// than 1.
+    pub fn new(a: f64, f: f64, k0: f64) -> Self {
+        assert!(
+            a.is_finite() && a > 0.0,
+            "PolarStereographic::new: equatorial radius {} is not positive",
+            a
+        );
+        assert!(f < 1.0, "PolarStereographic::new: polar semi-axis is not positive, f = {}", f);
+        assert!(k0.is_finite() && k0 > 0.0, "PolarStereographic::new: scale {} is not positive", k0);
+        let e2 = f * (2.0 - f);
+        let es = f.signum() * e2.abs().sqrt();
+        PolarStereographic {
+            a,
+            f,
+            k0,
+            e2,
+            es,
+            e2m: 1.0 - e2,
+            c: (1.0 - f) * geomath::eatanhe(1.0, es).exp(),
+        }
+    }
+
+    /// The UPS projection, the WGS84 ellipsoid with `k0` = 0.994.
+    pub fn ups() -> &'static Self {
+        UPS.get_or_init(|| PolarStereographic::new(WGS84_A, WGS84_F, UPS_K0))
+    }
+
+    /// Set the central scale factor so that the scale is `k` at latitude
+    /// `lat` in either hemisphere, e.g. to give a projection with a
+    /// standard parallel with `k` = 1.
+    ///
+    /// # Panics
+    /// Panics unless `k` is positive and finite and `lat` is in (-90&deg;,
+    /// 90&deg;].
+    pub fn set_scale(&mut self, lat: f64, k: f64) {
+        assert!(k.is_finite() && k > 0.0, "PolarStereographic::set_scale: scale {} is not positive", k);
+        assert!(
+            -90.0 < lat && lat <= 90.0,
+            "PolarStereographic::set_scale: latitude {} not in (-90d, 90d]",
+            lat
+        );
+        self.k0 = 1.0;
+        let (_, _, _, kold) = self.forward(true, lat.abs(), 0.0);
+        self.k0 *= k / kold;
+    }
+
+    /// The equatorial radius of the ellipsoid (meters).
+    pub fn equatorial_radius(&self) -> f64 {
+        self.a
+    }
+
+    /// The flattening of the ellipsoid.
+    pub fn flattening(&self) -> f64 {
+        self.f
+    }
+
+    /// The central scale factor, the scale at the pole.
+    pub fn central_scale(&self) -> f64 {
+        self.k0
+    }
+
+    // This converts to conformal coordinates with tau = tan(phi) and
+    // taup = tan(phip), where phip is the conformal latitude, then
+    //
+    //   rho = (2*k0*a/c) / (hypot(1, taup) + taup)
+    //   k = (rho/a) * secphi * sqrt((1-e2) + e2 / secphi^2)
+    //
+    // with c = (1 - f) * exp(e * atanh(e)).
+
+    /// Project (`lat`, `lon`) about the north pole if `northp` is true,
+    /// otherwise about the south pole. Points in the opposite hemisphere are
+    /// projected too, but with a rapidly growing scale.
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn forward(&self, northp: bool, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let lat = geomath::lat_fix(lat) * if northp { 1.0 } else { -1.0 };
+        let (slat, clat) = geomath::sincosd(lat);
+        let tau = if clat != 0.0 {
+            slat / clat
+        } else {
+            (1.0 / (f64::EPSILON * f64::EPSILON)).copysign(slat)
+        };
+        let secphi = 1.0_f64.hypot(tau);
+        let taup = geomath::taupf(tau, self.es);
+        let mut rho = 1.0_f64.hypot(taup) + taup.abs();
+        if taup >= 0.0 {
+            rho = if lat != 90.0 { 1.0 / rho } else { 0.0 };
+        }
+        rho *= 2.0 * self.k0 * self.a / self.c;
+        let k = if lat != 90.0 {
+            (rho / self.a) * secphi * (self.e2m + self.e2 / (secphi * secphi)).sqrt()
+        } else {
+            self.k0
+        };
+        let (x, y) = geomath::sincosd(lon);
+        let gamma = geomath::ang_normalize(if northp { lon } else { -lon });
+        (x * rho, y * if northp { -rho } else { rho }, gamma, k)
+    }
+
+    /// Find (`lat`, `lon`) for the point (`x`, `y`) projected about the north
+    /// pole if `northp` is true, otherwise about the south pole.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees).
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn reverse(&self, northp: bool, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let rho = x.hypot(y);
+        let t = if rho != 0.0 {
+            rho / (2.0 * self.k0 * self.a / self.c)
+        } else {
+            f64::EPSILON * f64::EPSILON
+        };
+        let taup = (1.0 / t - t) / 2.0;
+        let tau = geomath::tauf(taup, self.es);
+        let secphi = 1.0_f64.hypot(tau);
+        let k = if rho != 0.0 {
+            (rho / self.a) * secphi * (self.e2m + self.e2 / (secphi * secphi)).sqrt()
+        } else {
+            self.k0
+        };
+        let lat = if northp { 1.0 } else { -1.0 } * geomath::atan2d(tau, 1.0);
+        let lon = geomath::atan2d(x, if northp { -y } else { y });
+        let gamma = geomath::ang_normalize(if northp { lon } else { -lon });
+        (lat, lon, gamma, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        // Reference values from the conformal latitude in multiprecision
+        // arithmetic
+        let ps = PolarStereographic::ups();
+        let cases = [
+            ((true, 85.0, 30.0), (277728.69569133904, -481040.21165723475, 30.0, 0.99589479167497472)),
+            ((false, -75.0, -120.0), (-1450203.0076683501, -837275.09685692683, 120.0, 1.0112244306690608)),
+            ((true, 70.0, 135.0), (1585609.0117339392, 1585609.0117339392, 135.0, 1.0248921026954720)),
+            ((false, -87.5, 10.0), (48205.047225265081, 273384.40794245897, -10.0, 0.99447325605701394)),
+        ];
+        for &((northp, lat, lon), (x0, y0, gamma0, k0)) in cases.iter() {
+            let (x, y, gamma, k) = ps.forward(northp, lat, lon);
+            assert_relative_eq!(x, x0, epsilon = 1e-8);
+            assert_relative_eq!(y, y0, epsilon = 1e-8);
+            assert_eq!(gamma, gamma0);
+            assert_relative_eq!(k, k0, epsilon = 1e-15);
+
+            let (lat1, lon1, gamma1, k1) = ps.reverse(northp, x, y);
+            assert_relative_eq!(lat1, lat, epsilon = 1e-13);
+            assert_relative_eq!(lon1, lon, epsilon = 1e-13);
+            assert_relative_eq!(gamma1, gamma, epsilon = 1e-13);
+            assert_relative_eq!(k1, k, epsilon = 1e-15);
+        }
+    }
+
+    #[test]
+    fn test_poles() {
+        let ps = PolarStereographic::ups();
+        assert_eq!(ps.forward(true, 90.0, 45.0), (0.0, -0.0, 45.0, UPS_K0));
+        assert_eq!(ps.forward(false, -90.0, 45.0), (0.0, 0.0, -45.0, UPS_K0));
+        let (lat, _, _, k) = ps.reverse(true, 0.0, 0.0);
+        assert_eq!((lat, k), (90.0, UPS_K0));
+        let (lat, _, _, k) = ps.reverse(false, 0.0, 0.0);
+        assert_eq!((lat, k), (-90.0, UPS_K0));
+    }
+
+    #[test]
+    fn test_set_scale() {
+        let mut ps = PolarStereographic::new(WGS84_A, WGS84_F, 1.0);
+        ps.set_scale(-71.0, 1.0);
+        assert_relative_eq!(ps.central_scale(), 0.97276901289179694, epsilon = 1e-15);
+        let (_, _, _, k) = ps.forward(false, -71.0, 60.0);
+        assert_relative_eq!(k, 1.0, epsilon = 1e-15);
+        let (_, _, _, k) = ps.forward(true, 71.0, -60.0);
+        assert_relative_eq!(k, 1.0, epsilon = 1e-15);
+    }
+
+    #[test]
+    #[should_panic(expected = "scale 0 is not positive")]
+    fn test_zero_scale() {
+        PolarStereographic::new(WGS84_A, WGS84_F, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "equatorial radius")]
+    fn test_nan_radius() {
+        PolarStereographic::new(f64::NAN, WGS84_F, UPS_K0);
+    }
+
+    #[test]
+    #[should_panic(expected = "scale -1 is not positive")]
+    fn test_set_scale_negative() {
+        PolarStereographic::new(WGS84_A, WGS84_F, 1.0).set_scale(-71.0, -1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "scale NaN is not positive")]
+    fn test_set_scale_nan() {
+        PolarStereographic::new(WGS84_A, WGS84_F, 1.0).set_scale(-71.0, f64::NAN);
+    }
+
+    #[test]
+    #[should_panic(expected = "not in (-90d, 90d]")]
+    fn test_set_scale_south_pole() {
+        PolarStereographic::new(WGS84_A, WGS84_F, 1.0).set_scale(-90.0, 1.0);
+    }
+}
@@ -1,7 +1,7 @@
 use crate::geomath::ang_diff;
 use crate::geomath::ang_normalize;
-use crate::Geodesic;
-use crate::traits::{PolygonAreaCap};
+use crate::{Geodesic, Rhumb};
+use crate::traits::{PolygonAreaCap, PolylineCap};
 use crate::geodesic_capability as caps;
 
 const POLYGONAREA_MASK: u64 =
@@ -15,16 +15,124 @@ use accurate::traits::*;
 /// The standard winding of a Simple Feature polygon is counter-clockwise. However, if the polygon is a hole, then the winding is clockwise.
 /// ESRI Shapefile polygons are opposite, with the outer-ring being clockwise and holes being counter-clockwise.
 #[derive(Debug, Default, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Winding {
     Clockwise,
     #[default]
     CounterClockwise,
 }
 
+impl Winding {
+    /// The opposite winding, which is the winding of the holes of a polygon.
+    pub fn reverse(self) -> Winding {
+        match self {
+            Winding::Clockwise => Winding::CounterClockwise,
+            Winding::CounterClockwise => Winding::Clockwise,
+        }
+    }
+}
+
+/// The kind of line joining the vertices of a polygon.
+///
+/// This is implemented by [`Geodesic`], for polygons whose edges are
+/// geodesics, and by [`Rhumb`], for polygons whose edges are rhumb lines. It
+/// corresponds to the template parameter of GeographicLib's `PolygonAreaT`.
+pub trait PolygonEdge: Clone + std::fmt::Debug {
+    /// Solve the inverse problem for an edge.
+    ///
+    /// # Returns
+    /// - s12 length of the edge (meters).
+    /// - S12 area under the edge (meters<sup>2</sup>).
+    fn edge_inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64);
+
+    /// Solve the direct problem for an edge.
+    ///
+    /// # Returns
+    /// - lat2 latitude of the end of the edge (degrees).
+    /// - lon2 longitude of the end of the edge (degrees), unrolled so that
+    ///   `lon2 - lon1` is the longitude difference along the edge.
+    /// - S12 area under the edge (meters<sup>2</sup>).
+    fn edge_direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64);
+
+    /// The total area of the ellipsoid (meters<sup>2</sup>).
+    fn ellipsoid_area(&self) -> f64;
+
+    /// The length of an edge (meters), skipping the area computation.
+    fn edge_length(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64;
+
+    /// The end point (lat2, lon2) of an edge (degrees), skipping the area
+    /// computation.
+    fn edge_end(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64);
+}
+
+impl PolygonEdge for Geodesic {
+    fn edge_inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+        #[allow(non_snake_case)]
+        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) =
+            self._gen_inverse::<PolygonAreaCap>(lat1, lon1, lat2, lon2);
+        (s12, S12)
+    }
+
+    fn edge_direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64) {
+        #[allow(non_snake_case)]
+        let (_a12, lat2, lon2, _azi2, _s12, _m12, _M12, _M21, S12) =
+            self._gen_direct(lat1, lon1, azi1, false, s12, POLYGONAREA_MASK);
+        (lat2, lon2, S12)
+    }
+
+    fn ellipsoid_area(&self) -> f64 {
+        self.area()
+    }
+
+    fn edge_length(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+        #[allow(non_snake_case)]
+        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, _S12) =
+            self._gen_inverse::<PolylineCap>(lat1, lon1, lat2, lon2);
+        s12
+    }
+
+    fn edge_end(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64) {
+        #[allow(non_snake_case)]
+        let (_a12, lat2, lon2, _azi2, _s12, _m12, _M12, _M21, _S12) =
+            self._gen_direct(lat1, lon1, azi1, false, s12, caps::LATITUDE | caps::LONGITUDE);
+        (lat2, lon2)
+    }
+}
+
+impl<'a> PolygonEdge for Rhumb<'a> {
+    fn edge_inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64) {
+        #[allow(non_snake_case)]
+        let (s12, _azi12, S12) = self.inverse(lat1, lon1, lat2, lon2);
+        (s12, S12)
+    }
+
+    fn edge_direct(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64, f64) {
+        self.line(lat1, lon1, azi1)._gen_position(s12, true)
+    }
+
+    fn ellipsoid_area(&self) -> f64 {
+        Rhumb::ellipsoid_area(self)
+    }
+
+    fn edge_length(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+        let (s12, _azi12, _area) = self.inverse(lat1, lon1, lat2, lon2);
+        s12
+    }
+
+    fn edge_end(&self, lat1: f64, lon1: f64, azi1: f64, s12: f64) -> (f64, f64) {
+        let (lat2, lon2, _area) = self.direct(lat1, lon1, azi1, s12);
+        (lat2, lon2)
+    }
+}
+
 /// Compute the perimeter and area of a polygon on a Geodesic.
+///
+/// By default the edges of the polygon are geodesics. Create the PolygonArea
+/// with [`PolygonArea::with_edge`] and a [`Rhumb`] instead to compute the
+/// perimeter and area of a polygon whose edges are rhumb lines.
 #[derive(Debug, Clone)]
-pub struct PolygonArea<'a> {
-    geoid: &'a Geodesic,
+pub struct PolygonArea<'a, G: PolygonEdge = Geodesic> {
+    geoid: &'a G,
     winding: Winding,
     num: usize,
 
@@ -67,9 +175,38 @@ pub struct PolygonArea<'a> {
 /// assert_relative_eq!(perimeter, 443770.917248302);
 /// assert_relative_eq!(area, 12308778361.469452);
 /// ```
+///
+/// # Rhumb line example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, PolygonArea, Rhumb, Winding};
+///
+/// let g = Geodesic::wgs84();
+/// let rhumb = Rhumb::new(g);
+/// let mut pa = PolygonArea::with_edge(&rhumb, Winding::CounterClockwise);
+///
+/// // The edges follow the meridians and parallels
+/// pa.add_point(0.0, 0.0);
+/// pa.add_point(0.0, 1.0);
+/// pa.add_point(1.0, 1.0);
+/// pa.add_point(1.0, 0.0);
+///
+/// let (perimeter, area, _num) = pa.compute(false);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(perimeter, 443770.91767860356, epsilon = 1e-6);
+/// assert_relative_eq!(area, 12308463893.975352, epsilon = 1e-2);
+/// ```
 impl<'a> PolygonArea<'a> {
     /// Create a new PolygonArea using a Geodesic.
-    pub fn new(geoid: &'a Geodesic, winding: Winding) -> PolygonArea {
+    pub fn new(geoid: &'a Geodesic, winding: Winding) -> PolygonArea<'a> {
+        PolygonArea::with_edge(geoid, winding)
+    }
+}
+
+impl<'a, G: PolygonEdge> PolygonArea<'a, G> {
+    /// Create a new PolygonArea whose edges are given by `geoid`, e.g. a
+    /// Rhumb for rhumb line edges.
+    pub fn with_edge(geoid: &'a G, winding: Winding) -> Self {
         PolygonArea {
             geoid,
             winding,
@@ -103,12 +240,10 @@ impl<'a> PolygonArea<'a> {
             self.initial_lon = lon;
         } else {
             #[allow(non_snake_case)]
-            let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) = self
-                .geoid
-                ._gen_inverse::<PolygonAreaCap>(self.latest_lat, self.latest_lon, lat, lon);
+            let (s12, S12) = self.geoid.edge_inverse(self.latest_lat, self.latest_lon, lat, lon);
             self.perimetersum += s12;
             self.areasum += S12;
-            self.crossings += PolygonArea::transit(self.latest_lon, lon);
+            self.crossings += Self::transit(self.latest_lon, lon);
         }
         self.latest_lat = lat;
         self.latest_lon = lon;
@@ -125,17 +260,10 @@ impl<'a> PolygonArea<'a> {
         }
 
         #[allow(non_snake_case)]
-        let (_a12, lat, lon, _azi2, _s12, _m12, _M12, _M21, S12) = self.geoid._gen_direct(
-            self.latest_lat,
-            self.latest_lon,
-            azimuth,
-            false,
-            distance,
-            POLYGONAREA_MASK,
-        );
+        let (lat, lon, S12) = self.geoid.edge_direct(self.latest_lat, self.latest_lon, azimuth, distance);
         self.perimetersum += distance;
         self.areasum += S12;
-        self.crossings += PolygonArea::transitdirect(self.latest_lon, lon);
+        self.crossings += Self::transitdirect(self.latest_lon, lon);
         self.latest_lat = lat;
         self.latest_lon = lon;
         self.num += 1;
@@ -176,38 +304,84 @@ impl<'a> PolygonArea<'a> {
     /// // Over 5 trillion square meters!
     /// assert_eq!(area, 510053312945726.94);
     /// ```
-    pub fn compute(mut self, sign: bool) -> (f64, f64, usize) {
+    pub fn compute(self, sign: bool) -> (f64, f64, usize) {
+        self.current(sign)
+    }
+
+    /// Returns the perimeter and area of the polygon, closed with an edge back
+    /// to the first point, without consuming it. More points or edges may be
+    /// added afterwards.
+    ///
+    /// The tuple and the `sign` parameter are as for [`compute`](Self::compute).
+    ///
+    /// # Example
+    /// ```rust
+    /// use geographiclib_rs::{Geodesic, PolygonArea, Winding};
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let mut pa = PolygonArea::new(&g, Winding::CounterClockwise);
+    ///
+    /// // Running totals as the vertices arrive
+    /// for &(lat, lon) in [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)].iter() {
+    ///     pa.add_point(lat, lon);
+    ///     let (_perimeter, _area, _num) = pa.current(true);
+    /// }
+    ///
+    /// let (_perimeter, area, num) = pa.current(true);
+    ///
+    /// use approx::assert_relative_eq;
+    /// assert_relative_eq!(area, 12308778361.469452);
+    /// assert_eq!(num, 4);
+    /// ```
+    pub fn current(&self, sign: bool) -> (f64, f64, usize) {
+        let (perimetersum, areasum, crossings) = self.close();
+
+        // Properly take into account crossings when calculating area.
+        let areasum = self.reduce_area(areasum, crossings, sign);
+
+        (perimetersum, areasum, self.num)
+    }
+
+    // The perimeter, the unreduced area and the number of crossings of the
+    // prime meridian of the polygon closed with an edge back to the first point
+    fn close(&self) -> (f64, f64, i64) {
         #[allow(non_snake_case)]
-        let (_a12, s12, _salp1, _calp1, _salp2, _calp2, _m12, _M12, _M21, S12) =
-            self.geoid._gen_inverse::<PolygonAreaCap>(
-                self.latest_lat,
-                self.latest_lon,
-                self.initial_lat,
-                self.initial_lon,
-            );
-        self.perimetersum += s12;
-        self.areasum += S12;
+        let (s12, S12) =
+            self.geoid.edge_inverse(self.latest_lat, self.latest_lon, self.initial_lat, self.initial_lon);
+        let mut perimetersum = self.perimetersum;
+        let mut areasum = self.areasum;
+        perimetersum += s12;
+        areasum += S12;
+
+        #[cfg(feature = "accurate")]
+        let (perimetersum, areasum) = (perimetersum.sum(), areasum.sum());
+
+        let crossings = self.crossings + Self::transit(self.latest_lon, self.initial_lon);
+
+        (perimetersum, areasum, crossings)
+    }
 
-        let perimetersum;
-        let areasum;
+    /// Remove all the points, so the PolygonArea can be reused for another
+    /// polygon.
+    pub fn clear(&mut self) {
+        self.num = 0;
 
         #[cfg(not(feature = "accurate"))]
         {
-            perimetersum = self.perimetersum;
-            areasum = self.areasum;
+            self.areasum = 0.0;
+            self.perimetersum = 0.0;
         }
         #[cfg(feature = "accurate")]
         {
-            perimetersum = self.perimetersum.sum();
-            areasum = self.areasum.sum();
+            self.areasum = accurate::sum::Sum2::zero();
+            self.perimetersum = accurate::sum::Sum2::zero();
         }
 
-        self.crossings += PolygonArea::transit(self.latest_lon, self.initial_lon);
-
-        // Properly take into account crossings when calculating area.
-        let areasum = self.reduce_area(areasum, sign);
-
-        (perimetersum, areasum, self.num)
+        self.crossings = 0;
+        self.initial_lat = 0.0;
+        self.initial_lon = 0.0;
+        self.latest_lat = 0.0;
+        self.latest_lon = 0.0;
     }
 
     /// Check what the perimeter and area would be if this point was added to the polygon without actually adding it
@@ -259,13 +433,13 @@ impl<'a> PolygonArea<'a> {
         a - b
     }
 
-    fn reduce_area(&self, area: f64, signed: bool) -> f64 {
-        let geoid_area = self.geoid.area(); // Area of the planet
+    fn reduce_area(&self, area: f64, crossings: i64, signed: bool) -> f64 {
+        let geoid_area = self.geoid.ellipsoid_area(); // Area of the planet
         let mut area = area % geoid_area;
 
         // Translation of the following cpp code:
         // if (crossings & 1) area += (area < 0 ? 1 : -1) * _area0/2;
-        if self.crossings % 2 != 0 {
+        if crossings % 2 != 0 {
             if area < 0.0 {
                 area += geoid_area / 2.0;
             } else {
@@ -297,12 +471,405 @@ impl<'a> PolygonArea<'a> {
     }
 }
 
+// The state of a PolygonArea, without the reference to the ellipsoid
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PolygonAreaState {
+    winding: Winding,
+    num: usize,
+    // The sums as (value, remainder) pairs, so that no accuracy is lost
+    areasum: (f64, f64),
+    perimetersum: (f64, f64),
+    crossings: i64,
+    initial_lat: f64,
+    initial_lon: f64,
+    latest_lat: f64,
+    latest_lon: f64,
+}
+
+/// A PolygonArea serializes the points and sums accumulated so far, but not
+/// the Geodesic or Rhumb it uses. Restore it with
+/// [`PolygonArea::deserialize_state`].
+#[cfg(feature = "serde")]
+impl<'a, G: PolygonEdge> serde::Serialize for PolygonArea<'a, G> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[cfg(not(feature = "accurate"))]
+        let (areasum, perimetersum) = ((self.areasum, 0.0), (self.perimetersum, 0.0));
+        #[cfg(feature = "accurate")]
+        let (areasum, perimetersum) = {
+            let split = |x: accurate::sum::Sum2<f64>| (x.sum(), (x + -x.sum()).sum());
+            (split(self.areasum), split(self.perimetersum))
+        };
+
+        PolygonAreaState {
+            winding: self.winding,
+            num: self.num,
+            areasum,
+            perimetersum,
+            crossings: self.crossings,
+            initial_lat: self.initial_lat,
+            initial_lon: self.initial_lon,
+            latest_lat: self.latest_lat,
+            latest_lon: self.latest_lon,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a> PolygonArea<'a> {
+    /// Restore a serialized PolygonArea, which continues to use `geoid`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use geographiclib_rs::{Geodesic, PolygonArea, Winding};
+    ///
+    /// let g = Geodesic::wgs84();
+    /// let mut pa = PolygonArea::new(&g, Winding::CounterClockwise);
+    /// pa.add_point(0.0, 0.0);
+    /// pa.add_point(0.0, 1.0);
+    /// pa.add_point(1.0, 1.0);
+    ///
+    /// let json = serde_json::to_string(&pa).unwrap();
+    /// let mut de = serde_json::Deserializer::from_str(&json);
+    /// let mut restored = PolygonArea::deserialize_state(&g, &mut de).unwrap();
+    /// restored.add_point(1.0, 0.0);
+    ///
+    /// use approx::assert_relative_eq;
+    /// let (_perimeter, area, _num) = restored.compute(true);
+    /// assert_relative_eq!(area, 12308778361.469452, epsilon = 1e-3);
+    /// ```
+    pub fn deserialize_state<'de, D: serde::Deserializer<'de>>(
+        geoid: &'a Geodesic,
+        deserializer: D,
+    ) -> Result<PolygonArea<'a>, D::Error> {
+        PolygonArea::deserialize_state_with_edge(geoid, deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, G: PolygonEdge> PolygonArea<'a, G> {
+    /// Restore a serialized PolygonArea whose edges are given by `geoid`, see
+    /// [`PolygonArea::deserialize_state`].
+    pub fn deserialize_state_with_edge<'de, D: serde::Deserializer<'de>>(
+        geoid: &'a G,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        use serde::Deserialize;
+
+        let state = PolygonAreaState::deserialize(deserializer)?;
+        let mut pa = PolygonArea::with_edge(geoid, state.winding);
+        pa.num = state.num;
+
+        pa.areasum += state.areasum.0;
+        pa.areasum += state.areasum.1;
+        pa.perimetersum += state.perimetersum.0;
+        pa.perimetersum += state.perimetersum.1;
+
+        pa.crossings = state.crossings;
+        pa.initial_lat = state.initial_lat;
+        pa.initial_lon = state.initial_lon;
+        pa.latest_lat = state.latest_lat;
+        pa.latest_lon = state.latest_lon;
+        Ok(pa)
+    }
+}
+
+/// Compute the perimeter and area of polygons with holes, or of several
+/// polygons, on a Geodesic.
+///
+/// Like [`PolygonArea`], the edges are geodesics by default or rhumb lines if
+/// created with [`MultiPolygonArea::with_edge`] and a [`Rhumb`].
+///
+/// Each ring is added as a whole, as an exterior ring with
+/// [`add_exterior`](Self::add_exterior) or as a hole with
+/// [`add_interior`](Self::add_interior). Exterior rings are wound with the
+/// winding given at creation and holes with the opposite winding, see
+/// [`Winding`]. The area is the sum of the areas of the exterior rings less the
+/// areas of the holes, and the perimeter is the sum of the perimeters of all
+/// the rings.
+#[derive(Debug, Clone)]
+pub struct MultiPolygonArea<'a, G: PolygonEdge = Geodesic> {
+    exterior: PolygonArea<'a, G>,
+    interior: PolygonArea<'a, G>,
+    // The unreduced area and the number of crossings of each ring, and
+    // whether it is a hole
+    rings: Vec<(f64, i64, bool)>,
+    perimetersum: f64,
+    num: usize,
+}
+
+/// MultiPolygonArea can be used to compute the perimeter and area of
+/// polygons with holes on a Geodesic.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, MultiPolygonArea, Winding};
+///
+/// let g = Geodesic::wgs84();
+/// let mut mpa = MultiPolygonArea::new(&g, Winding::CounterClockwise);
+///
+/// // A 2 degree square with a 1 degree square hole, wound the other way
+/// mpa.add_exterior([(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)]);
+/// mpa.add_interior([(0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (0.5, 1.5)]);
+///
+/// let (_perimeter, area, num) = mpa.compute(true);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(area, 36924174572.75576, epsilon = 1e-3);
+/// assert_eq!(num, 8);
+/// ```
+impl<'a> MultiPolygonArea<'a> {
+    /// Create a new MultiPolygonArea using a Geodesic. `winding` is the
+    /// winding of the exterior rings.
+    pub fn new(geoid: &'a Geodesic, winding: Winding) -> MultiPolygonArea<'a> {
+        MultiPolygonArea::with_edge(geoid, winding)
+    }
+}
+
+impl<'a, G: PolygonEdge> MultiPolygonArea<'a, G> {
+    /// Create a new MultiPolygonArea whose edges are given by `geoid`, e.g. a
+    /// Rhumb for rhumb line edges. `winding` is the winding of the exterior
+    /// rings.
+    pub fn with_edge(geoid: &'a G, winding: Winding) -> Self {
+        MultiPolygonArea {
+            exterior: PolygonArea::with_edge(geoid, winding),
+            interior: PolygonArea::with_edge(geoid, winding.reverse()),
+            rings: Vec::new(),
+            perimetersum: 0.0,
+            num: 0,
+        }
+    }
+
+    /// Add an exterior ring given by its (lat, lon) points. The ring is closed
+    /// automatically, so the first point needn't be repeated at the end.
+    pub fn add_exterior<I: IntoIterator<Item = (f64, f64)>>(&mut self, ring: I) {
+        self.add_ring(ring, false);
+    }
+
+    /// Add a hole given by its (lat, lon) points. The ring is closed
+    /// automatically, so the first point needn't be repeated at the end.
+    pub fn add_interior<I: IntoIterator<Item = (f64, f64)>>(&mut self, ring: I) {
+        self.add_ring(ring, true);
+    }
+
+    fn add_ring<I: IntoIterator<Item = (f64, f64)>>(&mut self, ring: I, hole: bool) {
+        let pa = if hole { &mut self.interior } else { &mut self.exterior };
+        pa.clear();
+        for (lat, lon) in ring {
+            pa.add_point(lat, lon);
+        }
+        let (perimeter, area, crossings) = pa.close();
+        self.perimetersum += perimeter;
+        self.num += pa.num;
+        self.rings.push((area, crossings, hole));
+    }
+
+    /// Consumes the MultiPolygonArea and returns the following tuple:
+    ///  - 0: Perimeter in (meters) of all the rings.
+    ///  - 1: Area (meters²) of the exterior rings less the area of the holes.
+    ///  - 2: Number of points added in all the rings.
+    ///
+    /// `sign` is applied to each ring as in [`PolygonArea::compute`]. With
+    /// `sign = true` a ring wound against the convention has a negative area,
+    /// so a hole wound the same way as the exterior rings is added to the
+    /// total rather than subtracted.
+    pub fn compute(self, sign: bool) -> (f64, f64, usize) {
+        self.current(sign)
+    }
+
+    /// Returns the same tuple as [`compute`](Self::compute) without consuming
+    /// the MultiPolygonArea.
+    pub fn current(&self, sign: bool) -> (f64, f64, usize) {
+        let area = self
+            .rings
+            .iter()
+            .map(|&(area, crossings, hole)| {
+                if hole {
+                    -self.interior.reduce_area(area, crossings, sign)
+                } else {
+                    self.exterior.reduce_area(area, crossings, sign)
+                }
+            })
+            .sum();
+        (self.perimetersum, area, self.num)
+    }
+
+    /// Remove all the rings, so the MultiPolygonArea can be reused.
+    pub fn clear(&mut self) {
+        self.exterior.clear();
+        self.interior.clear();
+        self.rings.clear();
+        self.perimetersum = 0.0;
+        self.num = 0;
+    }
+}
+
+/// Compute the length of an open path, a polyline, on a Geodesic.
+///
+/// Unlike [`PolygonArea`] the path is not closed and the area is not
+/// computed, which saves the cost of the area terms for each edge. Like
+/// `PolygonArea`, the edges are geodesics by default or rhumb lines if
+/// created with [`Polyline::with_edge`] and a [`Rhumb`].
+#[derive(Debug, Clone)]
+pub struct Polyline<'a, G: PolygonEdge = Geodesic> {
+    geoid: &'a G,
+    num: usize,
+
+    #[cfg(not(feature = "accurate"))]
+    perimetersum: f64,
+
+    #[cfg(feature = "accurate")]
+    perimetersum: accurate::sum::Sum2<f64>,
+
+    latest_lat: f64,
+    latest_lon: f64,
+}
+
+/// Polyline can be used to compute the length of a path on a Geodesic, such
+/// as a GPS track.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, Polyline};
+///
+/// let g = Geodesic::wgs84();
+/// let mut pl = Polyline::new(&g);
+///
+/// pl.add_point(0.0, 0.0);
+/// pl.add_point(0.0, 1.0);
+/// pl.add_point(1.0, 1.0);
+/// pl.add_point(1.0, 0.0);
+///
+/// let (length, num) = pl.compute();
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(length, 333196.52869050315, epsilon = 1e-6);
+/// assert_eq!(num, 4);
+/// ```
+impl<'a> Polyline<'a> {
+    /// Create a new Polyline using a Geodesic.
+    pub fn new(geoid: &'a Geodesic) -> Polyline<'a> {
+        Polyline::with_edge(geoid)
+    }
+}
+
+impl<'a, G: PolygonEdge> Polyline<'a, G> {
+    /// Create a new Polyline whose edges are given by `geoid`, e.g. a Rhumb
+    /// for rhumb line edges.
+    pub fn with_edge(geoid: &'a G) -> Self {
+        Polyline {
+            geoid,
+
+            num: 0,
+
+            #[cfg(not(feature = "accurate"))]
+            perimetersum: 0.0,
+
+            #[cfg(feature = "accurate")]
+            perimetersum: accurate::sum::Sum2::zero(),
+
+            latest_lat: 0.0,
+            latest_lon: 0.0,
+        }
+    }
+
+    /// Add a point to the path
+    pub fn add_point(&mut self, lat: f64, lon: f64) {
+        if self.num > 0 {
+            self.perimetersum += self.geoid.edge_length(self.latest_lat, self.latest_lon, lat, lon);
+        }
+        self.latest_lat = lat;
+        self.latest_lon = lon;
+        self.num += 1;
+    }
+
+    /// Add an edge to the path using an azimuth (in degrees) and a distance (in meters). This can only be called after at least one point has been added.
+    ///
+    /// # Panics
+    /// Panics if no points have been added yet.
+    pub fn add_edge(&mut self, azimuth: f64, distance: f64) {
+        if self.num == 0 {
+            panic!("Polyline::add_edge: No points added yet");
+        }
+
+        let (lat, lon) = self.geoid.edge_end(self.latest_lat, self.latest_lon, azimuth, distance);
+        self.perimetersum += distance;
+        self.latest_lat = lat;
+        self.latest_lon = lon;
+        self.num += 1;
+    }
+
+    /// Consumes the Polyline and returns the following tuple:
+    ///  - 0: Length (meters) of the path.
+    ///  - 1: Number of points added to the path.
+    pub fn compute(self) -> (f64, usize) {
+        self.current()
+    }
+
+    /// Returns the length of the path and the number of points without
+    /// consuming the Polyline.
+    pub fn current(&self) -> (f64, usize) {
+        #[cfg(not(feature = "accurate"))]
+        let perimetersum = self.perimetersum;
+        #[cfg(feature = "accurate")]
+        let perimetersum = self.perimetersum.sum();
+
+        (perimetersum, self.num)
+    }
+
+    /// Remove all the points, so the Polyline can be reused for another path.
+    pub fn clear(&mut self) {
+        self.num = 0;
+
+        #[cfg(not(feature = "accurate"))]
+        {
+            self.perimetersum = 0.0;
+        }
+        #[cfg(feature = "accurate")]
+        {
+            self.perimetersum = accurate::sum::Sum2::zero();
+        }
+
+        self.latest_lat = 0.0;
+        self.latest_lon = 0.0;
+    }
+
+    /// Check what the length would be if this point was added to the path without actually adding it
+    pub fn test_point(&self, lat: f64, lon: f64) -> (f64, usize) {
+        let mut pl = self.clone();
+        pl.add_point(lat, lon);
+        pl.compute()
+    }
+
+    /// Check what the length would be if this edge was added to the path without actually adding it
+    pub fn test_edge(&self, azimuth: f64, distance: f64) -> (f64, usize) {
+        let mut pl = self.clone();
+        pl.add_edge(azimuth, distance);
+        pl.compute()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Geodesic;
+    use crate::{Geodesic, InverseGeodesic};
     use approx::assert_relative_eq;
 
+    #[test]
+    fn test_new_defaults_to_geodesic() {
+        let geoid = Geodesic::wgs84();
+        let pa: PolygonArea = PolygonArea::new(geoid, Winding::CounterClockwise);
+        let pl: Polyline = Polyline::new(geoid);
+        let mpa: MultiPolygonArea = MultiPolygonArea::new(geoid, Winding::CounterClockwise);
+        // The documented `new(&g, ..)` with `g = Geodesic::wgs84()` auto-derefs
+        #[allow(clippy::needless_borrow)]
+        let _: PolygonArea = PolygonArea::new(&geoid, Winding::CounterClockwise);
+        assert_eq!(pa.current(true).2, 0);
+        assert_eq!(pl.current().1, 0);
+        assert_eq!(mpa.current(true).2, 0);
+    }
+
     #[test]
     fn test_simple_polygonarea() {
         let geoid = Geodesic::wgs84();
@@ -331,6 +898,204 @@ mod tests {
         assert_relative_eq!(area, -12308778361.469, epsilon = 1.0e-3);
     }
 
+    #[test]
+    fn test_rhumb_polygonarea() {
+        let geoid = Geodesic::wgs84();
+        let rhumb = Rhumb::new(geoid);
+
+        // Edges along meridians and parallels, the area is exact
+        let mut pa = PolygonArea::with_edge(&rhumb, Winding::CounterClockwise);
+        pa.add_point(0.0, 0.0);
+        pa.add_point(0.0, 1.0);
+        pa.add_point(1.0, 1.0);
+        pa.add_point(1.0, 0.0);
+        let (perimeter, area, _count) = pa.compute(true);
+        assert_relative_eq!(perimeter, 443770.917678604, epsilon = 1.0e-6);
+        assert_relative_eq!(area, 12308463893.975352, epsilon = 1.0e-3);
+
+        // The same polygon built from edges
+        let mut pa = PolygonArea::with_edge(&rhumb, Winding::CounterClockwise);
+        pa.add_point(0.0, 0.0);
+        pa.add_edge(90.0, 111319.49079327357);
+        pa.add_edge(0.0, 110574.38855779878);
+        pa.add_edge(-90.0, 111302.6497697324);
+        let (perimeter, area, _count) = pa.compute(true);
+        assert_relative_eq!(perimeter, 443770.917678604, epsilon = 1.0e-6);
+        assert_relative_eq!(area, 12308463893.975352, epsilon = 1.0e-3);
+
+        // A polar cap bounded by the parallel at 80 degrees, which crosses
+        // the prime meridian
+        let mut pa = PolygonArea::with_edge(&rhumb, Winding::CounterClockwise);
+        pa.add_point(80.0, 0.0);
+        pa.add_point(80.0, 90.0);
+        pa.add_point(80.0, 180.0);
+        pa.add_point(80.0, 270.0);
+        let (perimeter, area, _count) = pa.clone().compute(true);
+        assert_relative_eq!(perimeter, 6981654.790127573, epsilon = 1.0e-6);
+        assert_relative_eq!(area, 3908572761836.572, epsilon = 1.0e-1);
+        let (_, area, _count) = pa.test_edge(90.0, 1745413.6975318932, true);
+        assert_relative_eq!(area, 3908572761836.572, epsilon = 1.0e-1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let geoid = Geodesic::wgs84();
+        assert_eq!(serde_json::to_string(&Winding::Clockwise).unwrap(), "\"Clockwise\"");
+
+        let mut pa = PolygonArea::new(geoid, Winding::Clockwise);
+        pa.add_point(89.0, 0.0);
+        pa.add_point(89.0, 90.0);
+        pa.add_point(89.0, 180.0);
+        let json = serde_json::to_string(&pa).unwrap();
+        let mut de = serde_json::Deserializer::from_str(&json);
+        let mut pa1 = PolygonArea::deserialize_state(geoid, &mut de).unwrap();
+        assert_eq!(pa1.current(true), pa.current(true));
+        pa.add_point(89.0, 270.0);
+        pa1.add_point(89.0, 270.0);
+        assert_eq!(pa1.compute(true), pa.compute(true));
+    }
+
+    #[test]
+    fn test_current_and_clear() {
+        let geoid = Geodesic::wgs84();
+        let mut pa = PolygonArea::new(geoid, Winding::CounterClockwise);
+
+        for _ in 0..2 {
+            pa.add_point(0.0, 0.0);
+            pa.add_point(0.0, 1.0);
+            assert_eq!(pa.current(true), pa.clone().compute(true));
+            pa.add_point(1.0, 1.0);
+            assert_eq!(pa.current(false), pa.clone().compute(false));
+            pa.add_point(1.0, 0.0);
+
+            let (perimeter, area, count) = pa.current(true);
+            assert_relative_eq!(perimeter, 443770.917, epsilon = 1.0e-3);
+            assert_relative_eq!(area, 12308778361.469, epsilon = 1.0e-3);
+            assert_eq!(count, 4);
+
+            // current doesn't close the stored polygon
+            pa.add_point(0.5, -0.5);
+            let (perimeter, area, count) = pa.current(true);
+            assert!(perimeter > 443770.917);
+            assert!(area > 12308778361.469);
+            assert_eq!(count, 5);
+
+            pa.clear();
+            assert_eq!(pa.current(true), (0.0, 0.0, 0));
+        }
+
+        // Crossings are reset by clear
+        pa.add_point(89.0, 0.0);
+        pa.add_point(89.0, 90.0);
+        pa.add_point(89.0, 180.0);
+        pa.add_point(89.0, 270.0);
+        pa.clear();
+        pa.add_point(0.0, 0.0);
+        pa.add_point(0.0, 1.0);
+        pa.add_point(1.0, 1.0);
+        pa.add_point(1.0, 0.0);
+        let (_, area, _) = pa.current(true);
+        assert_relative_eq!(area, 12308778361.469, epsilon = 1.0e-3);
+
+        let mut pl = Polyline::new(geoid);
+        pl.add_point(0.0, 0.0);
+        pl.add_point(0.0, 1.0);
+        assert_eq!(pl.current(), pl.clone().compute());
+        pl.clear();
+        assert_eq!(pl.current(), (0.0, 0));
+    }
+
+    #[test]
+    fn test_multipolygonarea() {
+        let geoid = Geodesic::wgs84();
+        let outer = [(0.0, 0.0), (0.0, 2.0), (2.0, 2.0), (2.0, 0.0)];
+        let hole = [(0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (0.5, 1.5)];
+        let far = [(-10.0, 179.0), (-10.0, -179.0), (-9.0, -179.0), (-9.0, 179.0)];
+
+        let ring = |winding: Winding, pts: &[(f64, f64)]| {
+            let mut pa = PolygonArea::new(geoid, winding);
+            for &(lat, lon) in pts.iter() {
+                pa.add_point(lat, lon);
+            }
+            pa.compute(true)
+        };
+        let (p_outer, a_outer, _) = ring(Winding::CounterClockwise, &outer);
+        let (p_hole, a_hole, _) = ring(Winding::Clockwise, &hole);
+        let (p_far, a_far, _) = ring(Winding::CounterClockwise, &far);
+        assert!(a_outer > 0.0 && a_hole > 0.0 && a_far > 0.0);
+
+        let mut mpa = MultiPolygonArea::new(geoid, Winding::CounterClockwise);
+        mpa.add_exterior(outer.iter().cloned());
+        mpa.add_interior(hole.iter().cloned());
+        let (perimeter, area, count) = mpa.current(true);
+        assert_relative_eq!(perimeter, p_outer + p_hole, epsilon = 1.0e-8);
+        assert_relative_eq!(area, a_outer - a_hole, epsilon = 1.0e-3);
+        assert_eq!(count, 8);
+        assert_eq!(mpa.current(false), (perimeter, area, count));
+
+        // A second polygon crossing the antimeridian
+        mpa.add_exterior(far.iter().cloned());
+        let (perimeter, area, count) = mpa.clone().compute(true);
+        assert_relative_eq!(perimeter, p_outer + p_hole + p_far, epsilon = 1.0e-8);
+        assert_relative_eq!(area, a_outer - a_hole + a_far, epsilon = 1.0e-3);
+        assert_eq!(count, 12);
+
+        // The same rings with shapefile winding
+        let mut mpa_cw = MultiPolygonArea::new(geoid, Winding::Clockwise);
+        mpa_cw.add_exterior(outer.iter().rev().cloned());
+        mpa_cw.add_interior(hole.iter().rev().cloned());
+        mpa_cw.add_exterior(far.iter().rev().cloned());
+        let (_, area_cw, _) = mpa_cw.compute(true);
+        assert_relative_eq!(area_cw, area, epsilon = 1.0e-3);
+
+        mpa.clear();
+        assert_eq!(mpa.current(true), (0.0, 0.0, 0));
+    }
+
+    #[test]
+    fn test_polyline() {
+        let geoid = Geodesic::wgs84();
+        let pts = [(0.0, 0.0), (0.0, 1.0), (1.0, 1.0), (1.0, 0.0)];
+
+        let mut pl = Polyline::new(geoid);
+        let mut pa = PolygonArea::new(geoid, Winding::CounterClockwise);
+        for &(lat, lon) in pts.iter() {
+            pl.add_point(lat, lon);
+            pa.add_point(lat, lon);
+        }
+        let (closing, _, _, _) = geoid.inverse(1.0, 0.0, 0.0, 0.0);
+        let (length, count) = pl.clone().compute();
+        let (perimeter, _area, _count) = pa.compute(true);
+        assert_relative_eq!(length, perimeter - closing, epsilon = 1.0e-8);
+        assert_eq!(count, 4);
+
+        // The length of the path if it returned to the start
+        let (length, count) = pl.test_point(0.0, 0.0);
+        assert_relative_eq!(length, perimeter, epsilon = 1.0e-8);
+        assert_eq!(count, 5);
+        let (length, _count) = pl.test_edge(180.0, closing);
+        assert_relative_eq!(length, perimeter, epsilon = 1.0e-8);
+
+        // Rhumb line edges along the meridians and parallels
+        let rhumb = Rhumb::new(geoid);
+        let mut pl = Polyline::with_edge(&rhumb);
+        pl.add_point(0.0, 0.0);
+        pl.add_edge(90.0, 111319.49079327357);
+        pl.add_edge(0.0, 110574.38855779878);
+        pl.add_point(1.0, 0.0);
+        let (length, count) = pl.compute();
+        assert_relative_eq!(length, 333196.5291209535, epsilon = 1.0e-6);
+        assert_eq!(count, 4);
+
+        let pl = Polyline::new(geoid);
+        assert_eq!(pl.test_point(1.0, 1.0), (0.0, 1));
+        let result = std::panic::catch_unwind(|| {
+            let (_, _) = pl.test_edge(90.0, 1000.0);
+        });
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_planimeter0() {
         // Copied from https://github.com/geographiclib/geographiclib-octave/blob/0662e05a432a040a60ab27c779fa09b554177ba9/inst/geographiclib_test.m#L644
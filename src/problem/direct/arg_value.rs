@@ -1,5 +1,6 @@
 
 
+/// The azimuth at the first point of the direct problem.
 pub trait DirectProblemAzimuth: Sized + Clone + Copy {
     const AZI_ONLY: bool;
 
@@ -27,6 +28,7 @@ pub trait DirectProblemAzimuth: Sized + Clone + Copy {
     }
 }
 
+/// An azimuth in degrees.
 #[derive(Copy,Clone)]
 pub struct AzimuthOnly {
     azi: f64,
@@ -45,6 +47,7 @@ impl DirectProblemAzimuth for AzimuthOnly {
     fn get_azimuth(&self) -> f64 { self.azi }
 }
 
+/// An azimuth in degrees, along with its already computed sine and cosine.
 #[derive(Copy,Clone)]
 pub struct AzimuthAndAngles {
     azi: f64,
@@ -81,6 +84,7 @@ pub trait DirectProblemDistanceArg: Sized + Clone + Copy {
     fn get_s12(&self) -> f64 { f64::NAN }
 }
 
+/// A distance in meters.
 #[derive(Copy,Clone)]
 pub struct DistanceIn {
     s12: f64,
@@ -99,6 +103,7 @@ impl DirectProblemDistanceArg for DistanceIn {
     fn get_s12(&self) -> f64 { self.s12 }
 }
 
+/// An arc length in degrees.
 #[derive(Copy,Clone)]
 pub struct ArcIn {
     a12: f64,
@@ -1,4 +1,9 @@
+//! The direct geodesic problem, with the outputs chosen at compile time.
 
-pub (in crate) mod ret_value;
-pub (in crate) mod arg_value;
-pub (in crate) mod problem;
+pub mod ret_value;
+pub mod arg_value;
+pub mod problem;
+
+pub use self::arg_value::*;
+pub use self::problem::direct_problem;
+pub use self::ret_value::*;
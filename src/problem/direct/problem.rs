@@ -27,7 +27,26 @@ use crate::{
 /// optimizations which can occur as there is still going
 /// to be a phi node in the IR, which can limit
 /// propigation of values and optimizations.
-#[allow(dead_code)]
+///
+/// `A` is the starting azimuth, see [`AzimuthOnly`](super::AzimuthOnly).
+/// `D` is either a distance ([`DistanceIn`](super::DistanceIn)) or an
+/// arc length ([`ArcIn`](super::ArcIn)). `R` selects the outputs, and
+/// is built by nesting the [`ret_value`](super::ret_value) types.
+///
+/// ```rust
+/// use geographiclib_rs::Geodesic;
+/// use geographiclib_rs::problem::direct::{direct_problem, AzimuthOnly, Azi, DistanceIn, LatLon};
+///
+/// // Example, determine the point 10000 km NE of JFK:
+/// let g = Geodesic::wgs84();
+/// let out: Azi<LatLon> = direct_problem(g, 40.64, -73.78, AzimuthOnly::from(45.0), DistanceIn::from(10e6));
+///
+/// use approx::assert_relative_eq;
+/// use geographiclib_rs::problem::direct::DirectProblemReturnValue;
+/// assert_relative_eq!(out.get_lat2(), 32.621100463725796);
+/// assert_relative_eq!(out.get_lon2(), 49.052487092959836);
+/// assert_relative_eq!(out.get_azi2(), 140.4059858768007);
+/// ```
 #[inline(always)]
 pub fn direct_problem<A,D,R>(
     geod: &Geodesic,
@@ -43,9 +62,7 @@ where
 {
     use crate::traits::caps::{
         Latitude as CapLat,
-        Longitude as CapLon,
         Azimuth as CapAzi,
-        LongUnroll as CapUnroll,
     };
     /*
      * Apply several defaults
@@ -55,10 +72,9 @@ where
      * So we force set them.
      *
      */
-    direct_problem_core::<A,D,R,CapUnroll<CapAzi<CapLon<CapLat<R::Features>>>>>(geod, lat1, lon1, azi, dist)
+    direct_problem_core::<A,D,R,CapAzi<CapLat<R::Features>>>(geod, lat1, lon1, azi, dist)
 }
 
-#[allow(dead_code)]
 #[inline(always)]
 fn direct_problem_core<A,D,R,C>(
     geod: &Geodesic,
@@ -95,8 +111,9 @@ where
     let mut _B11 = 0.0;
     let mut _stau1 = 0.0;
     let mut _ctau1 = 0.0;
-    if C::C1 {
-        _B11 = geod.weights.calc_single_bxf::<C,C1Coeff>(eps,ssig1, csig1);
+    // a distance input always needs the C1 coefficients
+    if C::C1 || !D::ARC_MODE {
+        _B11 = geod.weights.calc_single_bxf::<DistanceIn<C>,C1Coeff>(eps,ssig1, csig1);
         let s = _B11.sin();
         let c = _B11.cos();
         _stau1 = ssig1 * c + csig1 * s;
@@ -140,7 +157,7 @@ where
         if geod.f.abs() > 0.01 {
             ssig2 = ssig1 * csig12 + csig1 * ssig12;
             csig2 = csig1 * csig12 - ssig1 * ssig12;
-            B12 = geod.weights.calc_single_bxf::<C,C1Coeff>(eps,ssig2, csig2);
+            B12 = geod.weights.calc_single_bxf::<DistanceIn<C>,C1Coeff>(eps,ssig2, csig2);
             let serr = a1 * (sig12 + (B12 - _B11)) - dist.get_s12() / geod._b;
             sig12 -= serr / (1.0 + k2 * ssig2.powi(2)).sqrt();
             ssig12 = sig12.sin();
@@ -240,7 +257,7 @@ where
     }
 
     ret.set_a12(
-        if C::ARC_MODE { 
+        if D::ARC_MODE {
             dist.get_a12()
         } else {
             sig12.to_degrees() 
@@ -255,22 +272,17 @@ fn test_direct_100points() {
     use approx::assert_relative_eq;
 
     use crate::test_data::{
-        TestData,DirectTest,
         geod_test_100::GeodTest_100,
     };
     use crate::problem::direct::ret_value::{
         LatLon,Azi,ArcDist,M12,Area,Dist,
     };
-    use crate::problem::direct::arg_value::AzimuthOnly;
-    use crate::traits::caps::{
-        Empty,
-    };
 
 
     let earth = Geodesic::wgs84();
 
     for point in GeodTest_100.iter() {
-        let (v1,v2,v3,_) = point.direct_tests();
+        let (v1,_v2,v3,_) = point.direct_tests();
 
         let out: Dist<Azi<ArcDist<M12<Area<LatLon>>>>> = direct_problem(earth, v1.lat1, v1.lon1, v1.azi, v1.dist);
         assert_relative_eq!(v1.lon2, out.get_lon2(), epsilon = 1e-8f64);
@@ -290,3 +302,43 @@ fn test_direct_100points() {
     }
 }
 
+
+#[test]
+fn test_direct_problem_matches_gen_direct() {
+    use approx::assert_relative_eq;
+    use crate::geodesic_capability as caps;
+    use crate::problem::direct::ret_value::{
+        LatLon,Azi,ArcDist,M12,M12M21,Dist,
+    };
+    use crate::problem::direct::arg_value::{AzimuthOnly,DistanceIn,ArcIn};
+
+    let earth = Geodesic::wgs84();
+    let (a12, lat2, lon2, azi2, s12, m12, M12, M21, _S12) = earth._gen_direct(
+        40.64, 170.0, 45.0, false, 10e6,
+        caps::STANDARD | caps::REDUCEDLENGTH | caps::GEODESICSCALE,
+    );
+
+    // M21 is passed through the outer combinators
+    let out: Dist<ArcDist<M12<M12M21<Azi<LatLon>>>>> = direct_problem(earth, 40.64, 170.0, AzimuthOnly::from(45.0), DistanceIn::from(10e6));
+    assert_eq!(out.get_lat2(), lat2);
+    assert_eq!(out.get_lon2(), lon2);
+    assert_eq!(out.get_azi2(), azi2);
+    assert_eq!(out.get_s12(), s12);
+    assert_eq!(out.get_a12(), a12);
+    assert_eq!(out.get_m12(), m12);
+    assert_eq!(out.get_M12(), M12);
+    assert_eq!(out.get_M21(), M21);
+    assert!(out.get_M12() != out.get_M21());
+    assert!(lon2 < 180.0);
+
+    // LatLon alone still needs the coefficients for a distance input
+    let out: LatLon = direct_problem(earth, 40.64, 170.0, AzimuthOnly::from(45.0), DistanceIn::from(10e6));
+    assert_eq!(out.get_lat2(), lat2);
+    assert_eq!(out.get_lon2(), lon2);
+
+    let out: Dist<ArcDist<LatLon>> = direct_problem(earth, 40.64, 170.0, AzimuthOnly::from(45.0), ArcIn::from(a12));
+    assert_eq!(out.get_a12(), a12);
+    assert_relative_eq!(out.get_s12(), s12, epsilon = 1e-8);
+    assert_relative_eq!(out.get_lat2(), lat2, epsilon = 1e-12);
+    assert_relative_eq!(out.get_lon2(), lon2, epsilon = 1e-12);
+}
@@ -10,6 +10,7 @@ use crate::traits::caps::{
     GeodesicScale as CapScale,
     Area as CapArea,
     Distance as CapDist,
+    LongUnroll as CapUnroll,
 };
 
 /// This type is used to control what data is returned from
@@ -41,7 +42,7 @@ use crate::traits::caps::{
 /// * S12: the area under the path travelled in squared meters
 /// * m12/M12/M21: differential quantities used within the calculation to approximate the local curvature.
 ///
-pub (in crate) trait DirectProblemReturnValue: Default {
+pub trait DirectProblemReturnValue: Default {
     type Features: Caps;
 
     /// a12 is the arc distance (in degrees) between 2 points
@@ -88,7 +89,9 @@ pub (in crate) trait DirectProblemReturnValue: Default {
 }
 
 
-pub(in crate) struct LatLon {
+/// Returns lat2 and lon2. The innermost type of every return value.
+#[derive(Clone, Copy, Debug)]
+pub struct LatLon {
     lat2: f64,
     lon2: f64,
 }
@@ -114,7 +117,9 @@ impl DirectProblemReturnValue for LatLon {
     fn get_lon2(&self) -> f64 { self.lon2 }
 }
 
-pub(in crate) struct Dist<D: DirectProblemReturnValue> {
+/// Adds s12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Dist<D: DirectProblemReturnValue> {
     s12: f64,
     other: D,
 }
@@ -183,12 +188,14 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for Dist<D> {
     #[inline(always)]
     fn get_M12(&self) -> f64 { self.other.get_M12() }
     #[inline(always)]
-    fn get_M21(&self) -> f64 { self.other.get_M12() }
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
     #[inline(always)]
     fn get_S12(&self) -> f64 { self.other.get_S12() }
 }
 
-pub(in crate) struct ArcDist<D: DirectProblemReturnValue> {
+/// Adds a12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct ArcDist<D: DirectProblemReturnValue> {
     a12: f64,
     other: D,
 }
@@ -257,14 +264,16 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for ArcDist<D> {
     #[inline(always)]
     fn get_M12(&self) -> f64 { self.other.get_M12() }
     #[inline(always)]
-    fn get_M21(&self) -> f64 { self.other.get_M12() }
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
     #[inline(always)]
     fn get_S12(&self) -> f64 { self.other.get_S12() }
 }
 
 
 
-pub(in crate) struct M12<D: DirectProblemReturnValue> {
+/// Adds the reduced length m12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct M12<D: DirectProblemReturnValue> {
     m12: f64,
     other: D,
 }
@@ -333,12 +342,14 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for M12<D> {
     #[inline(always)]
     fn get_M12(&self) -> f64 { self.other.get_M12() }
     #[inline(always)]
-    fn get_M21(&self) -> f64 { self.other.get_M12() }
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
     #[inline(always)]
     fn get_S12(&self) -> f64 { self.other.get_S12() }
 }
 
-pub(in crate) struct M12M21<D: DirectProblemReturnValue> {
+/// Adds the geodesic scales M12 and M21 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct M12M21<D: DirectProblemReturnValue> {
     M12: f64,
     M21: f64,
     other: D,
@@ -415,7 +426,9 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for M12M21<D> {
     fn get_S12(&self) -> f64 { self.other.get_S12() }
 }
 
-pub(in crate) struct Area<D: DirectProblemReturnValue> {
+/// Adds the area S12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Area<D: DirectProblemReturnValue> {
     S12: f64,
     other: D,
 }
@@ -489,7 +502,9 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for Area<D> {
     fn get_S12(&self) -> f64 { self.S12 }
 }
 
-pub(in crate) struct Azi<D: DirectProblemReturnValue> {
+/// Adds azi2 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Azi<D: DirectProblemReturnValue> {
     azi2: f64,
     other: D,
 }
@@ -562,3 +577,73 @@ impl<D: DirectProblemReturnValue> DirectProblemReturnValue for Azi<D> {
     #[inline(always)]
     fn get_S12(&self) -> f64 { self.other.get_S12() }
 }
+
+/// Unrolls lon2 for `D`.
+///
+/// Rather than being reduced to [-180°, 180°], lon2 is lon1 plus the
+/// longitude travelled, so it tells how many times the geodesic circled
+/// the ellipsoid.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unroll<D: DirectProblemReturnValue> {
+    other: D,
+}
+impl<D: DirectProblemReturnValue> DirectProblemReturnValue for Unroll<D> {
+    type Features = CapUnroll<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_lat2(&mut self, lat2: f64) {
+        self.other.set_lat2(lat2);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+    #[inline(always)]
+    fn set_azi2(&mut self, azi2: f64) {
+        self.other.set_azi2(azi2);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_lat2(&self) -> f64 { self.other.get_lat2() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+    #[inline(always)]
+    fn get_azi2(&self) -> f64 { self.other.get_azi2() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+}
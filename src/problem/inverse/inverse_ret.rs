@@ -1,6 +1,44 @@
+#![allow(non_snake_case)]
 
+use crate::geomath;
+use crate::traits::caps::{
+    Caps,
+    Empty,
+    Longitude as CapLon,
+    Azimuth as CapAzi,
+    ReducedLength as CapRedLen,
+    GeodesicScale as CapScale,
+    Area as CapArea,
+    Distance as CapDist,
+    LongUnroll as CapUnroll,
+};
 
-pub (in crate) trait InverseProblemReturnValue: Default {
+/// This type is used to control what data is returned from
+/// the inverse problem.
+///
+/// The "Inverse Geodesic Problem" can be defined roughly
+///
+/// > I am at point (lat1, lon1) and want to get to (lat2, lon2).
+/// > How far is it, and in which direction do I set out?
+///
+/// The distance is s12 (in meters), and the direction is given by
+/// the azimuth azi1 at the first point. The azimuth azi2 at the
+/// second point is the direction you'd be travelling on arrival.
+/// The azimuths are found as their sine and cosine (salp/calp),
+/// which [`InverseProblemReturnValue::get_azi1`] and
+/// [`InverseProblemReturnValue::get_azi2`] convert to degrees.
+///
+/// Full details:
+///
+/// * a12: Arc Distance travelled (in degrees).
+/// * s12: Distance travelled (in meters).
+/// * salp1/calp1: sine and cosine of the azimuth at the 1st point.
+/// * salp2/calp2: sine and cosine of the azimuth at the 2nd point.
+/// * S12: the area under the path travelled in squared meters
+/// * m12/M12/M21: differential quantities used within the calculation to approximate the local curvature.
+/// * lon1/lon2: the longitudes of the points, reduced to [-180, 180] unless unrolled.
+///
+pub trait InverseProblemReturnValue: Default {
     type Features: Caps;
 
     #[inline(always)]
@@ -12,15 +50,21 @@ pub (in crate) trait InverseProblemReturnValue: Default {
     #[inline(always)]
     fn set_calp1(&mut self, _calp1: f64) { }
     #[inline(always)]
-    fn set_salp2(&mut self, _salp1: f64) { }
+    fn set_salp2(&mut self, _salp2: f64) { }
     #[inline(always)]
-    fn set_calp2(&mut self, _calp1: f64) { }
+    fn set_calp2(&mut self, _calp2: f64) { }
     #[inline(always)]
     fn set_m12(&mut self, _m12: f64) { }
     #[inline(always)]
     fn set_M12(&mut self, _M12: f64) { }
     #[inline(always)]
     fn set_M21(&mut self, _M21: f64) { }
+    #[inline(always)]
+    fn set_S12(&mut self, _S12: f64) { }
+    #[inline(always)]
+    fn set_lon1(&mut self, _lon1: f64) { }
+    #[inline(always)]
+    fn set_lon2(&mut self, _lon2: f64) { }
 
 
     #[inline(always)]
@@ -41,4 +85,706 @@ pub (in crate) trait InverseProblemReturnValue: Default {
     fn get_M12(&self) -> f64 { f64::NAN }
     #[inline(always)]
     fn get_M21(&self) -> f64 { f64::NAN }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { f64::NAN }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { f64::NAN }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { f64::NAN }
+
+    /// Azimuth at the 1st point (degrees).
+    #[inline(always)]
+    fn get_azi1(&self) -> f64 { geomath::atan2d(self.get_salp1(), self.get_calp1()) }
+    /// Azimuth at the 2nd point (degrees).
+    #[inline(always)]
+    fn get_azi2(&self) -> f64 { geomath::atan2d(self.get_salp2(), self.get_calp2()) }
+}
+
+/// Returns a12, which is always computed. The innermost type of every return value.
+#[derive(Clone, Copy, Debug)]
+pub struct Arc {
+    a12: f64,
+}
+impl Default for Arc {
+    fn default() -> Self {
+        Self {
+            a12: f64::NAN,
+        }
+    }
+}
+impl InverseProblemReturnValue for Arc {
+    type Features = Empty;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) { self.a12 = a12; }
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.a12 }
+}
+
+/// Adds s12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Dist<D: InverseProblemReturnValue> {
+    s12: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for Dist<D> {
+    fn default() -> Self {
+        Self {
+            s12: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for Dist<D> {
+    type Features = CapDist<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.s12 = s12;
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.s12 }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+}
+
+/// Adds the sine and cosine of the azimuths, salp1/calp1 and salp2/calp2, to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Alp<D: InverseProblemReturnValue> {
+    salp1: f64,
+    calp1: f64,
+    salp2: f64,
+    calp2: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for Alp<D> {
+    fn default() -> Self {
+        Self {
+            salp1: f64::NAN,
+            calp1: f64::NAN,
+            salp2: f64::NAN,
+            calp2: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for Alp<D> {
+    type Features = CapAzi<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.salp1 = salp1;
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.calp1 = calp1;
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.salp2 = salp2;
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.calp2 = calp2;
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.salp1 }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.calp1 }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.salp2 }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.calp2 }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+}
+
+/// Adds the reduced length m12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct M12<D: InverseProblemReturnValue> {
+    m12: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for M12<D> {
+    fn default() -> Self {
+        Self {
+            m12: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for M12<D> {
+    type Features = CapRedLen<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.m12 = m12;
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.m12 }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+}
+
+/// Adds the geodesic scales M12 and M21 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct M12M21<D: InverseProblemReturnValue> {
+    M12: f64,
+    M21: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for M12M21<D> {
+    fn default() -> Self {
+        Self {
+            M12: f64::NAN,
+            M21: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for M12M21<D> {
+    type Features = CapScale<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.M12 = M12;
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.M21 = M21;
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.M12 }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.M21 }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+}
+
+/// Adds the area S12 to `D`.
+#[derive(Clone, Copy, Debug)]
+pub struct Area<D: InverseProblemReturnValue> {
+    S12: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for Area<D> {
+    fn default() -> Self {
+        Self {
+            S12: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for Area<D> {
+    type Features = CapArea<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.S12 = S12;
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.S12 }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
+}
+
+/// Adds the longitudes lon1 and lon2 to `D`, reduced to [-180°, 180°]
+/// unless [`Unroll`] is used as well.
+#[derive(Clone, Copy, Debug)]
+pub struct Lon<D: InverseProblemReturnValue> {
+    lon1: f64,
+    lon2: f64,
+    other: D,
+}
+impl<D: InverseProblemReturnValue> Default for Lon<D> {
+    fn default() -> Self {
+        Self {
+            lon1: f64::NAN,
+            lon2: f64::NAN,
+            other: D::default(),
+        }
+    }
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for Lon<D> {
+    type Features = CapLon<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.lon1 = lon1;
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.lon2 = lon2;
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.lon1 }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.lon2 }
+}
+
+/// Unrolls the longitudes returned by [`Lon`].
+///
+/// lon1 is returned as given, and lon2 is lon1 plus the longitude
+/// difference, so lon2 - lon1 is the longitude travelled.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Unroll<D: InverseProblemReturnValue> {
+    other: D,
+}
+impl<D: InverseProblemReturnValue> InverseProblemReturnValue for Unroll<D> {
+    type Features = CapUnroll<D::Features>;
+
+    #[inline(always)]
+    fn set_a12(&mut self, a12: f64) {
+        self.other.set_a12(a12);
+    }
+    #[inline(always)]
+    fn set_s12(&mut self, s12: f64) {
+        self.other.set_s12(s12);
+    }
+    #[inline(always)]
+    fn set_salp1(&mut self, salp1: f64) {
+        self.other.set_salp1(salp1);
+    }
+    #[inline(always)]
+    fn set_calp1(&mut self, calp1: f64) {
+        self.other.set_calp1(calp1);
+    }
+    #[inline(always)]
+    fn set_salp2(&mut self, salp2: f64) {
+        self.other.set_salp2(salp2);
+    }
+    #[inline(always)]
+    fn set_calp2(&mut self, calp2: f64) {
+        self.other.set_calp2(calp2);
+    }
+    #[inline(always)]
+    fn set_m12(&mut self, m12: f64) {
+        self.other.set_m12(m12);
+    }
+    #[inline(always)]
+    fn set_M12(&mut self, M12: f64) {
+        self.other.set_M12(M12);
+    }
+    #[inline(always)]
+    fn set_M21(&mut self, M21: f64) {
+        self.other.set_M21(M21);
+    }
+    #[inline(always)]
+    fn set_S12(&mut self, S12: f64) {
+        self.other.set_S12(S12);
+    }
+    #[inline(always)]
+    fn set_lon1(&mut self, lon1: f64) {
+        self.other.set_lon1(lon1);
+    }
+    #[inline(always)]
+    fn set_lon2(&mut self, lon2: f64) {
+        self.other.set_lon2(lon2);
+    }
+
+
+    #[inline(always)]
+    fn get_a12(&self) -> f64 { self.other.get_a12() }
+    #[inline(always)]
+    fn get_s12(&self) -> f64 { self.other.get_s12() }
+    #[inline(always)]
+    fn get_salp1(&self) -> f64 { self.other.get_salp1() }
+    #[inline(always)]
+    fn get_calp1(&self) -> f64 { self.other.get_calp1() }
+    #[inline(always)]
+    fn get_salp2(&self) -> f64 { self.other.get_salp2() }
+    #[inline(always)]
+    fn get_calp2(&self) -> f64 { self.other.get_calp2() }
+    #[inline(always)]
+    fn get_m12(&self) -> f64 { self.other.get_m12() }
+    #[inline(always)]
+    fn get_M12(&self) -> f64 { self.other.get_M12() }
+    #[inline(always)]
+    fn get_M21(&self) -> f64 { self.other.get_M21() }
+    #[inline(always)]
+    fn get_S12(&self) -> f64 { self.other.get_S12() }
+    #[inline(always)]
+    fn get_lon1(&self) -> f64 { self.other.get_lon1() }
+    #[inline(always)]
+    fn get_lon2(&self) -> f64 { self.other.get_lon2() }
 }
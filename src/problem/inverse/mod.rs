@@ -1,3 +1,7 @@
+//! The inverse geodesic problem, with the outputs chosen at compile time.
 
-//pub mod problem;
 pub mod inverse_ret;
+pub mod problem;
+
+pub use self::inverse_ret::*;
+pub use self::problem::inverse_problem;
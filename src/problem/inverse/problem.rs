@@ -1,8 +1,129 @@
+#![allow(non_snake_case)]
 
+use crate::{
+    geodesic::{Geodesic},
+    geomath::{self},
+    traits::caps::{Caps},
+    problem::inverse::{
+        inverse_ret::{InverseProblemReturnValue},
+    },
+};
 
-pub fn inverse_problem<C>(
+/// The function that solves the inverse problem.
+///
+/// The counterpart of [`direct_problem`](crate::problem::direct::direct_problem).
+/// `R` selects the outputs, and is built by nesting the
+/// [`inverse_ret`](super::inverse_ret) types. The solver is
+/// monomorphized over `R::Features`, so only the selected
+/// outputs are calculated.
+///
+/// The longitudes are only returned by [`Lon`](super::Lon), and are
+/// unrolled if [`Unroll`](super::Unroll) is also used.
+///
+/// ```rust
+/// use geographiclib_rs::Geodesic;
+/// use geographiclib_rs::problem::inverse::{inverse_problem, Alp, Arc, Dist};
+///
+/// // Example, determine the distance between JFK and Singapore Changi Airport:
+/// let g = Geodesic::wgs84();
+/// let out: Dist<Alp<Arc>> = inverse_problem(g, 40.64, -73.78, 1.36, 103.99);
+///
+/// use approx::assert_relative_eq;
+/// use geographiclib_rs::problem::inverse::InverseProblemReturnValue;
+/// assert_relative_eq!(out.get_s12(), 15347512.94051294);
+/// assert_relative_eq!(out.get_azi1(), 3.3057734780176125, epsilon = 1e-13);
+/// assert_relative_eq!(out.get_azi2(), 177.48784020815515, epsilon = 1e-13);
+/// ```
+#[inline(always)]
+pub fn inverse_problem<R>(
+    geod: &Geodesic,
     lat1: f64,
     lon1: f64,
     lat2: f64,
     lon2: f64,
-) ->
+) -> R
+where
+    R: InverseProblemReturnValue + Sized,
+{
+    let (a12, s12, salp1, calp1, salp2, calp2, m12, M12, M21, S12) =
+        geod._gen_inverse::<R::Features>(lat1, lon1, lat2, lon2);
+
+    /*
+     * Only the combinators which hold a value do anything
+     * with it, the rest of these calls compile away.
+     */
+    let mut ret: R = R::default();
+    if R::Features::LONGITUDE {
+        let (lon1, lon2) = geomath::inverse_lons(lon1, lon2, R::Features::LONG_UNROLL);
+        ret.set_lon1(lon1);
+        ret.set_lon2(lon2);
+    }
+    ret.set_a12(a12);
+    ret.set_s12(s12);
+    ret.set_salp1(salp1);
+    ret.set_calp1(calp1);
+    ret.set_salp2(salp2);
+    ret.set_calp2(calp2);
+    ret.set_m12(m12);
+    ret.set_M12(M12);
+    ret.set_M21(M21);
+    ret.set_S12(S12);
+    ret
+}
+
+#[test]
+fn test_inverse_problem_matches_gen_inverse() {
+    use crate::problem::inverse::inverse_ret::{
+        Arc,Alp,Dist,M12,M12M21,Area,
+    };
+    use crate::traits::caps::{All};
+
+    let earth = Geodesic::wgs84();
+    let (a12, s12, salp1, calp1, salp2, calp2, m12, M12, M21, S12) =
+        earth._gen_inverse::<All>(40.64, -73.78, 1.36, 103.99);
+
+    let out: Dist<Alp<M12<M12M21<Area<Arc>>>>> = inverse_problem(earth, 40.64, -73.78, 1.36, 103.99);
+    assert_eq!(out.get_a12(), a12);
+    assert_eq!(out.get_s12(), s12);
+    assert_eq!(out.get_salp1(), salp1);
+    assert_eq!(out.get_calp1(), calp1);
+    assert_eq!(out.get_salp2(), salp2);
+    assert_eq!(out.get_calp2(), calp2);
+    assert_eq!(out.get_m12(), m12);
+    assert_eq!(out.get_M12(), M12);
+    assert_eq!(out.get_M21(), M21);
+    assert_eq!(out.get_S12(), S12);
+
+    // Outputs which weren't asked for are NaN
+    let out: M12M21<Arc> = inverse_problem(earth, 40.64, -73.78, 1.36, 103.99);
+    assert_eq!(out.get_a12(), a12);
+    assert_eq!(out.get_M12(), M12);
+    assert_eq!(out.get_M21(), M21);
+    assert!(out.get_s12().is_nan());
+    assert!(out.get_azi1().is_nan());
+    assert!(out.get_S12().is_nan());
+}
+
+#[test]
+fn test_inverse_problem_unroll() {
+    use approx::assert_relative_eq;
+    use crate::problem::inverse::inverse_ret::{
+        Arc,Dist,Lon,Unroll,
+    };
+
+    // See GeodSolve29
+    let earth = Geodesic::wgs84();
+    let out: Dist<Lon<Arc>> = inverse_problem(earth, 0.0, 539.0, 0.0, 181.0);
+    assert_relative_eq!(out.get_lon1(), 179.0, epsilon = 1e-10);
+    assert_relative_eq!(out.get_lon2(), -179.0, epsilon = 1e-10);
+    assert_relative_eq!(out.get_s12(), 222639.0, epsilon = 0.5);
+
+    let out: Unroll<Dist<Lon<Arc>>> = inverse_problem(earth, 0.0, 539.0, 0.0, 181.0);
+    assert_relative_eq!(out.get_lon1(), 539.0, epsilon = 1e-10);
+    assert_relative_eq!(out.get_lon2(), 541.0, epsilon = 1e-10);
+    assert_relative_eq!(out.get_s12(), 222639.0, epsilon = 0.5);
+
+    // Without Lon, Unroll has nothing to do
+    let out: Unroll<Dist<Arc>> = inverse_problem(earth, 0.0, 539.0, 0.0, 181.0);
+    assert!(out.get_lon2().is_nan());
+}
@@ -1,5 +1,7 @@
+//! Generic, monomorphized solvers for the geodesic problems.
 
-pub(in crate) mod direct;
-//pub(in crate) mod inverse;
+
+pub mod direct;
+pub mod inverse;
 //pub(in crate) mod shared;
 //pub(in crate) mod utils;
@@ -0,0 +1,775 @@
+use crate::geomath::{self, EllipticFunction};
+use crate::internals::constants::{ALP_COEFF, BET_COEFF, RHUMB_R_COEFF};
+use crate::internals::utils::aux_series;
+use crate::Geodesic;
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+const DEGREE: f64 = PI / 180.0;
+
+/// Solve the direct and inverse problems for rhumb lines on an ellipsoid.
+///
+/// A rhumb line (or loxodrome) is a line of constant azimuth, the course
+/// steered by a ship keeping a fixed compass heading. Unlike a geodesic it
+/// is not the shortest path between two points, but it is a straight line
+/// on a Mercator chart.
+///
+/// The distance along the rhumb line is found from the difference in the
+/// rectifying latitude, and the longitude from the difference in the
+/// isometric latitude. These are expressed in terms of the conformal latitude
+/// with Krüger's series in the third flattening `n`, carried to sixth order,
+/// which are accurate to round-off for `|f| < 0.01`. For larger flattening,
+/// or if the solver is created with `Rhumb::new_exact`, the rectifying latitude
+/// is instead found from the elliptic integral of the second kind and the
+/// area from a Fourier series whose coefficients are computed numerically.
+/// Differences are computed with divided differences so that the results stay
+/// accurate for short lines and for lines close to a parallel.
+///
+/// See C. F. F. Karney,
+/// [The area of rhumb polygons](https://doi.org/10.1002/sapm.12233),
+/// Stud. Appl. Math. 151(1), 220&ndash;236 (2023).
+#[derive(Debug, Clone)]
+pub struct Rhumb<'a> {
+    geod: &'a Geodesic,
+    es: f64,
+    alp: [f64; 7],
+    bet: [f64; 7],
+    // Coefficients of the area series, r[0] is unused
+    r: Vec<f64>,
+    // Elliptic function for the meridian distance in terms of the parametric
+    // latitude, set for the exact solution in place of the series
+    ell: Option<EllipticFunction>,
+    // Rectifying radius times degree, so that s12 = mu12 * rm
+    rm: f64,
+    c2: f64,
+}
+
+/// Rhumb computes the direct and inverse rhumb line problems on the
+/// ellipsoid of a `Geodesic`.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, Rhumb};
+///
+/// let g = Geodesic::wgs84();
+/// let rhumb = Rhumb::new(g);
+///
+/// // Steering a constant course from JFK to LHR
+/// let (s12, azi12, _area) = rhumb.inverse(40.6, -73.8, 51.6, -0.5);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(s12, 5771083.383328, epsilon = 1e-6);
+/// assert_relative_eq!(azi12, 77.76838971026, epsilon = 1e-10);
+///
+/// let (lat2, lon2, _area) = rhumb.direct(40.6, -73.8, azi12, s12);
+/// assert_relative_eq!(lat2, 51.6, epsilon = 1e-12);
+/// assert_relative_eq!(lon2, -0.5, epsilon = 1e-12);
+/// ```
+impl<'a> Rhumb<'a> {
+    /// Create a new Rhumb solver using the ellipsoid of a Geodesic.
+    ///
+    /// The series solution is used for `|f| <= 0.01` and the exact solution
+    /// otherwise.
+    pub fn new(geod: &'a Geodesic) -> Self {
+        Self::with_exact(geod, geod.f.abs() > 0.01)
+    }
+
+    /// Create a new Rhumb solver using the exact solution whatever the
+    /// flattening of the ellipsoid of a Geodesic.
+    ///
+    /// This is slower than the series solution, which `Rhumb::new` already
+    /// avoids when it is not accurate.
+    pub fn new_exact(geod: &'a Geodesic) -> Self {
+        Self::with_exact(geod, true)
+    }
+
+    fn with_exact(geod: &'a Geodesic, exact: bool) -> Self {
+        let n = geod._n;
+        let es = geod.f.signum() * geod._e2.abs().sqrt();
+        let (ell, r, rm) = if exact {
+            // The meridian distance is b * E(beta, k) with k^2 = -e'^2
+            let ell = EllipticFunction::new(-geod._ep2);
+            let rm = geod._b * ell.e() / FRAC_PI_2 * DEGREE;
+            (Some(ell), area_coeffs(geod._e2, es), rm)
+        } else {
+            let rm = geod._b * (1.0 + geomath::_A1m1f(n)) * DEGREE;
+            (None, aux_series(n, &RHUMB_R_COEFF).to_vec(), rm)
+        };
+        Rhumb {
+            geod,
+            es,
+            alp: aux_series(n, &ALP_COEFF),
+            bet: aux_series(n, &BET_COEFF),
+            r,
+            ell,
+            rm,
+            c2: geod.area() / 720.0,
+        }
+    }
+
+    /// Solve the direct rhumb line problem.
+    ///
+    /// # Arguments
+    /// - lat1 latitude of point 1 (degrees).
+    /// - lon1 longitude of point 1 (degrees).
+    /// - azi12 azimuth of the rhumb line (degrees).
+    /// - s12 distance between point 1 and point 2 (meters); it can be negative.
+    ///
+    /// # Returns
+    /// - lat2 latitude of point 2 (degrees).
+    /// - lon2 longitude of point 2 (degrees), in the range [-180, 180].
+    /// - S12 area under the rhumb line (meters<sup>2</sup>).
+    ///
+    /// If the rhumb line passes over a pole, `lat2` is the latitude of the
+    /// point reached by continuing over the pole and `lon2` and `S12` are NaN.
+    pub fn direct(&self, lat1: f64, lon1: f64, azi12: f64, s12: f64) -> (f64, f64, f64) {
+        self.line(lat1, lon1, azi12).position(s12)
+    }
+
+    /// Solve the inverse rhumb line problem.
+    ///
+    /// # Arguments
+    /// - lat1 latitude of point 1 (degrees).
+    /// - lon1 longitude of point 1 (degrees).
+    /// - lat2 latitude of point 2 (degrees).
+    /// - lon2 longitude of point 2 (degrees).
+    ///
+    /// # Returns
+    /// - s12 rhumb distance between point 1 and point 2 (meters).
+    /// - azi12 azimuth of the rhumb line (degrees).
+    /// - S12 area under the rhumb line (meters<sup>2</sup>).
+    ///
+    /// The shorter of the two rhumb lines joining the points, the one with
+    /// `|lon12| <= 180`, is returned.
+    pub fn inverse(&self, lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> (f64, f64, f64) {
+        let (lon12, _) = geomath::ang_diff(lon1, lon2);
+        let psi1 = self.isometric_latitude(lat1);
+        let psi2 = self.isometric_latitude(lat2);
+        let psi12 = if psi1 == psi2 { 0.0 } else { psi2 - psi1 };
+        let azi12 = geomath::atan2d(lon12, psi12);
+        let s12 = if psi12.is_infinite() {
+            // One end at a pole where the rhumb line is a meridian
+            (self.rectifying_latitude(lat2) - self.rectifying_latitude(lat1)).abs() * self.rm
+        } else {
+            lon12.hypot(psi12)
+                * self.d_isometric_to_rectifying(lat2, lat1, psi2 * DEGREE, psi1 * DEGREE)
+                * self.rm
+        };
+        let s_12 = self.c2 * lon12 * self.mean_sin_xi(psi2 * DEGREE, psi1 * DEGREE);
+        (s12, azi12, s_12)
+    }
+
+    /// Create a RhumbLine starting at (`lat1`, `lon1`) with azimuth `azi12`.
+    pub fn line(&self, lat1: f64, lon1: f64, azi12: f64) -> RhumbLine<'_> {
+        RhumbLine::new(self, lat1, lon1, azi12)
+    }
+
+    /// The equatorial radius of the ellipsoid (meters).
+    pub fn equatorial_radius(&self) -> f64 {
+        self.geod.a
+    }
+
+    /// The flattening of the ellipsoid.
+    pub fn flattening(&self) -> f64 {
+        self.geod.f
+    }
+
+    /// The total area of the ellipsoid (meters<sup>2</sup>).
+    pub fn ellipsoid_area(&self) -> f64 {
+        self.geod.area()
+    }
+
+    // tan(chi) for latitude lat (degrees)
+    fn taup(&self, lat: f64) -> f64 {
+        let (s, c) = geomath::sincosd(geomath::lat_fix(lat));
+        geomath::taupf(s / c, self.es)
+    }
+
+    // Isometric latitude (degrees), infinite at the poles
+    fn isometric_latitude(&self, lat: f64) -> f64 {
+        self.taup(lat).asinh() / DEGREE
+    }
+
+    // Rectifying latitude (degrees)
+    fn rectifying_latitude(&self, lat: f64) -> f64 {
+        match &self.ell {
+            Some(ell) => {
+                let (sphi, cphi) = geomath::sincosd(geomath::lat_fix(lat));
+                let (sbet, cbet) = (self.geod._f1 * sphi, cphi);
+                let h = sbet.hypot(cbet);
+                let (sbet, cbet) = (sbet / h, cbet / h);
+                90.0 * ell.e_sncndn(sbet, cbet, ell.delta(sbet, cbet)) / ell.e()
+            }
+            None => {
+                let chi = self.taup(lat).atan();
+                (chi + sin_series(chi, &self.alp)) / DEGREE
+            }
+        }
+    }
+
+    // Latitude (degrees) for rectifying latitude mu (radians)
+    fn inverse_rectifying_latitude(&self, mu: f64) -> f64 {
+        match &self.ell {
+            Some(ell) => {
+                // Solve E(beta) = E * mu / (pi/2) for the parametric latitude
+                // beta with Newton's method, starting from beta = mu
+                const NUMIT: usize = 10;
+                let tol = f64::EPSILON.sqrt() / 10.0;
+                let e = ell.e() * mu / FRAC_PI_2;
+                let mut beta = mu;
+                for _ in 0..NUMIT {
+                    let (sbet, cbet) = beta.sin_cos();
+                    let dn = ell.delta(sbet, cbet);
+                    let d = (ell.e_sncndn(sbet, cbet, dn) - e) / dn;
+                    beta -= d;
+                    if d.abs() < tol {
+                        break;
+                    }
+                }
+                let (sbet, cbet) = beta.sin_cos();
+                geomath::atan2d(sbet, self.geod._f1 * cbet)
+            }
+            None => {
+                let chi = mu - sin_series(mu, &self.bet);
+                geomath::tauf(chi.tan(), self.es).atan() / DEGREE
+            }
+        }
+    }
+
+    // Divided difference of mu with respect to psi (both in radians) between
+    // latitudes latx and laty (degrees)
+    fn d_isometric_to_rectifying(&self, latx: f64, laty: f64, psix: f64, psiy: f64) -> f64 {
+        match &self.ell {
+            Some(ell) => self.d_rectifying(ell, latx, laty) / self.d_isometric(latx, laty),
+            None => {
+                let (chix, chiy) = (gd(psix), gd(psiy));
+                (1.0 + d_sin_series(chix, chiy, &self.alp)) * d_gd(psix, psiy)
+            }
+        }
+    }
+
+    // Divided difference of psi with respect to mu (both in radians) between
+    // latitudes latx and laty (degrees)
+    fn d_rectifying_to_isometric(&self, latx: f64, laty: f64, mux: f64, muy: f64) -> f64 {
+        match &self.ell {
+            Some(ell) => self.d_isometric(latx, laty) / self.d_rectifying(ell, latx, laty),
+            None => {
+                let chix = mux - sin_series(mux, &self.bet);
+                let chiy = muy - sin_series(muy, &self.bet);
+                d_gdinv(chix.tan(), chiy.tan()) * (1.0 - d_sin_series(mux, muy, &self.bet))
+            }
+        }
+    }
+
+    // Divided difference of mu with respect to phi for the exact solution
+    fn d_rectifying(&self, ell: &EllipticFunction, latx: f64, laty: f64) -> f64 {
+        let f1 = self.geod._f1;
+        let (tbetx, tbety) = (f1 * tand(latx), f1 * tand(laty));
+        FRAC_PI_2 * f1 * d_e(ell, tbetx.atan(), tbety.atan())
+            * d_tan(latx * DEGREE, laty * DEGREE)
+            * d_atan(tbetx, tbety)
+            / ell.e()
+    }
+
+    // Divided difference of psi with respect to phi, with
+    // psi = asinh(tan(phi)) - eatanhe(sin(phi))
+    fn d_isometric(&self, latx: f64, laty: f64) -> f64 {
+        let (phix, phiy) = (latx * DEGREE, laty * DEGREE);
+        d_asinh(tand(latx), tand(laty)) * d_tan(phix, phiy)
+            - self.d_eatanhe(phix.sin(), phiy.sin()) * d_sin(phix, phiy)
+    }
+
+    // Divided difference of eatanhe(x)
+    fn d_eatanhe(&self, x: f64, y: f64) -> f64 {
+        let e2 = self.geod._e2;
+        let (t, d) = (x - y, 1.0 - e2 * x * y);
+        if t == 0.0 {
+            e2 / d
+        } else if d > 0.0 {
+            geomath::eatanhe(t / d, self.es) / t
+        } else {
+            // Only for prolate ellipsoids with e^2 < -1, where the subtraction
+            // formula for atan picks up a multiple of pi
+            (geomath::eatanhe(x, self.es) - geomath::eatanhe(y, self.es)) / t
+        }
+    }
+
+    // The mean value of sin(xi) between psix and psiy (radians), where xi
+    // is the authalic latitude
+    fn mean_sin_xi(&self, psix: f64, psiy: f64) -> f64 {
+        if psix.is_infinite() || psiy.is_infinite() {
+            return if psix.is_finite() {
+                psiy.signum()
+            } else if psiy.is_finite() || psix == psiy {
+                psix.signum()
+            } else {
+                0.0
+            };
+        }
+        d_log(psix.cosh(), psiy.cosh()) * d_cosh(psix, psiy)
+            + d_cos_series(gd(psix), gd(psiy), &self.r) * d_gd(psix, psiy)
+    }
+}
+
+/// A rhumb line with a given starting point and azimuth.
+///
+/// Positions along the line are found with `position`, which is cheaper than
+/// repeated calls to `Rhumb::direct` since the quantities depending only on
+/// the starting point are computed once.
+#[derive(Debug, Clone)]
+pub struct RhumbLine<'a> {
+    rh: &'a Rhumb<'a>,
+    lat1: f64,
+    lon1: f64,
+    azi12: f64,
+    salp: f64,
+    calp: f64,
+    // rectifying and isometric latitudes of the start (degrees)
+    mu1: f64,
+    psi1: f64,
+    // radius of the circle of latitude at the start (meters)
+    r1: f64,
+}
+
+/// RhumbLine computes points along a rhumb line.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{Geodesic, Rhumb};
+///
+/// let g = Geodesic::wgs84();
+/// let rhumb = Rhumb::new(g);
+///
+/// // Waypoints every 1000 km heading north-east from JFK
+/// let line = rhumb.line(40.6, -73.8, 45.0);
+/// let (lat, lon, _area) = line.position(1e6);
+///
+/// use approx::assert_relative_eq;
+/// let (lat2, lon2, _area) = rhumb.direct(40.6, -73.8, 45.0, 1e6);
+/// assert_relative_eq!(lat, lat2, epsilon = 1e-12);
+/// assert_relative_eq!(lon, lon2, epsilon = 1e-12);
+/// ```
+impl<'a> RhumbLine<'a> {
+    /// Create a new RhumbLine starting at (`lat1`, `lon1`) with azimuth `azi12`.
+    pub fn new(rh: &'a Rhumb<'a>, lat1: f64, lon1: f64, azi12: f64) -> Self {
+        let lat1 = geomath::lat_fix(lat1);
+        let azi12 = geomath::ang_normalize(azi12);
+        let (salp, calp) = geomath::sincosd(azi12);
+        let (sphi, cphi) = geomath::sincosd(lat1);
+        let r1 = rh.geod.a * cphi / (1.0 - rh.geod._e2 * sphi * sphi).sqrt();
+        RhumbLine {
+            rh,
+            lat1,
+            lon1,
+            azi12,
+            salp,
+            calp,
+            mu1: rh.rectifying_latitude(lat1),
+            psi1: rh.isometric_latitude(lat1),
+            r1,
+        }
+    }
+
+    /// Find the point at distance `s12` (meters) along the rhumb line.
+    ///
+    /// # Returns
+    /// - lat2 latitude of point 2 (degrees).
+    /// - lon2 longitude of point 2 (degrees), in the range [-180, 180].
+    /// - S12 area under the rhumb line (meters<sup>2</sup>).
+    ///
+    /// If the rhumb line passes over a pole, `lat2` is the latitude of the
+    /// point reached by continuing over the pole and `lon2` and `S12` are NaN.
+    pub fn position(&self, s12: f64) -> (f64, f64, f64) {
+        self._gen_position(s12, false)
+    }
+
+    // As position, with lon2 unrolled to lon1 + lon12 if unroll is set
+    pub(in crate) fn _gen_position(&self, s12: f64, unroll: bool) -> (f64, f64, f64) {
+        let rh = self.rh;
+        let mu12 = s12 * self.calp / rh.rm;
+        let mut mu2 = self.mu1 + mu12;
+        let (lat2, lon12, s_12) = if mu2.abs() <= 90.0 {
+            let (lat2, lon12, psi2) = if self.calp != 0.0 {
+                let lat2 = rh.inverse_rectifying_latitude(mu2 * DEGREE);
+                let psi12 =
+                    rh.d_rectifying_to_isometric(lat2, self.lat1, mu2 * DEGREE, self.mu1 * DEGREE) * mu12;
+                (lat2, self.salp * psi12 / self.calp, self.psi1 + psi12)
+            } else {
+                (self.lat1, self.salp * s12 / (self.r1 * DEGREE), self.psi1)
+            };
+            let s_12 = rh.c2 * lon12 * rh.mean_sin_xi(self.psi1 * DEGREE, psi2 * DEGREE);
+            (lat2, lon12, s_12)
+        } else {
+            // Reduce to the interval [-180, 180)
+            mu2 = geomath::ang_normalize(mu2);
+            // Deal with points on the anti-meridian
+            if mu2.abs() > 90.0 {
+                mu2 = geomath::ang_normalize(180.0 - mu2);
+            }
+            (rh.inverse_rectifying_latitude(mu2 * DEGREE), f64::NAN, f64::NAN)
+        };
+        let lon2 = if unroll {
+            self.lon1 + lon12
+        } else {
+            geomath::ang_normalize(geomath::ang_normalize(self.lon1) + lon12)
+        };
+        (lat2, lon2, s_12)
+    }
+
+    /// The latitude of the starting point (degrees).
+    pub fn latitude(&self) -> f64 {
+        self.lat1
+    }
+
+    /// The longitude of the starting point (degrees).
+    pub fn longitude(&self) -> f64 {
+        self.lon1
+    }
+
+    /// The azimuth of the rhumb line (degrees).
+    pub fn azimuth(&self) -> f64 {
+        self.azi12
+    }
+}
+
+// sum(c[j] * sin(2*j*x))
+fn sin_series(x: f64, c: &[f64; 7]) -> f64 {
+    c.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(j, cj)| cj * (2.0 * j as f64 * x).sin())
+        .sum()
+}
+
+// sin(x)/x, accurate near 0
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        x.sin() / x
+    }
+}
+
+// Divided difference of sum(c[j] * sin(2*j*x))
+fn d_sin_series(x: f64, y: f64, c: &[f64; 7]) -> f64 {
+    let (p, d) = (x + y, x - y);
+    c.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(j, cj)| {
+            let j = j as f64;
+            cj * 2.0 * j * (j * p).cos() * sinc(j * d)
+        })
+        .sum()
+}
+
+// Divided difference of sum(c[j] * cos(2*j*x))
+fn d_cos_series(x: f64, y: f64, c: &[f64]) -> f64 {
+    let (p, d) = (x + y, x - y);
+    c.iter()
+        .enumerate()
+        .skip(1)
+        .map(|(j, cj)| {
+            let j = j as f64;
+            -cj * 2.0 * j * (j * p).sin() * sinc(j * d)
+        })
+        .sum()
+}
+
+// Coefficients r[j] of sum(r[j] * cos(2*j*chi)) whose derivative with respect
+// to psi is sin(xi) - sin(chi), for the exact solution. They are found from a
+// discrete sine transform of (sin(xi) - sin(chi)) / cos(chi), doubling the
+// number of terms until the last half are negligible.
+fn area_coeffs(e2: f64, es: f64) -> Vec<f64> {
+    const MAXL: usize = 1 << 10;
+    // q(phi) / (1 - e^2) in terms of sin(phi), sin(xi) = q(phi) / q(pi/2)
+    let q = |s: f64| s / (1.0 - e2 * s * s) + if e2 == 0.0 { s } else { geomath::eatanhe(s, es) / e2 };
+    let qp = q(1.0);
+    let mut l = 16;
+    loop {
+        let h: Vec<f64> = (0..l)
+            .map(|k| {
+                let chi = (2 * k + 1) as f64 * PI / (4 * l) as f64;
+                let (schi, cchi) = chi.sin_cos();
+                let tau = geomath::tauf(schi / cchi, es);
+                (q(tau / 1.0_f64.hypot(tau)) / qp - schi) / cchi
+            })
+            .collect();
+        let r: Vec<f64> = (0..=l)
+            .map(|j| {
+                if j == 0 {
+                    return 0.0;
+                }
+                let b: f64 = h
+                    .iter()
+                    .enumerate()
+                    .map(|(k, hk)| hk * ((j * (2 * k + 1)) as f64 * PI / (2 * l) as f64).sin())
+                    .sum();
+                let b = b * if j < l { 2.0 } else { 1.0 } / l as f64;
+                -b / (2 * j) as f64
+            })
+            .collect();
+        let tail = r[l / 2 + 1..].iter().fold(0.0_f64, |m, rj| m.max(rj.abs()));
+        if tail <= f64::EPSILON / 2.0 || l >= MAXL {
+            return r[..=l / 2].to_vec();
+        }
+        l *= 2;
+    }
+}
+
+// tan(x) for x in degrees, large but finite at the poles
+fn tand(x: f64) -> f64 {
+    let (s, c) = geomath::sincosd(x);
+    if c != 0.0 {
+        s / c
+    } else {
+        s.signum() / f64::EPSILON.powi(2)
+    }
+}
+
+// The Gudermannian function
+fn gd(x: f64) -> f64 {
+    x.sinh().atan()
+}
+
+// Divided difference of log(x)
+fn d_log(x: f64, y: f64) -> f64 {
+    let t = x - y;
+    if t != 0.0 {
+        2.0 * (t / (x + y)).atanh() / t
+    } else {
+        1.0 / x
+    }
+}
+
+// Divided difference of sinh(x)
+fn d_sinh(x: f64, y: f64) -> f64 {
+    let d = (x - y) / 2.0;
+    ((x + y) / 2.0).cosh() * if d != 0.0 { d.sinh() / d } else { 1.0 }
+}
+
+// Divided difference of cosh(x)
+fn d_cosh(x: f64, y: f64) -> f64 {
+    let d = (x - y) / 2.0;
+    ((x + y) / 2.0).sinh() * if d != 0.0 { d.sinh() / d } else { 1.0 }
+}
+
+// Divided difference of sin(x)
+fn d_sin(x: f64, y: f64) -> f64 {
+    ((x + y) / 2.0).cos() * sinc((x - y) / 2.0)
+}
+
+// Divided difference of tan(x)
+fn d_tan(x: f64, y: f64) -> f64 {
+    let (d, tx, ty) = (x - y, x.tan(), y.tan());
+    let txy = tx * ty;
+    if d == 0.0 {
+        1.0 + txy
+    } else if 2.0 * txy > -1.0 {
+        (1.0 + txy) * d.tan() / d
+    } else {
+        (tx - ty) / d
+    }
+}
+
+// Divided difference of E(x), the incomplete integral of the second kind.
+// For x and y on the same side of the equator use the addition theorem,
+// https://dlmf.nist.gov/19.11.E2 and https://dlmf.nist.gov/19.11.E4, with
+// E(x) - E(y) = E(z) - k^2 * sin(x) * sin(y) * sin(z).
+fn d_e(ell: &EllipticFunction, x: f64, y: f64) -> f64 {
+    let d = x - y;
+    let (sx, cx) = x.sin_cos();
+    let (sy, cy) = y.sin_cos();
+    let (dnx, dny) = (ell.delta(sx, cx), ell.delta(sy, cy));
+    if x * y <= 0.0 {
+        return if d != 0.0 {
+            (ell.e_sncndn(sx, cx, dnx) - ell.e_sncndn(sy, cy, dny)) / d
+        } else {
+            1.0
+        };
+    }
+    // tan(z/2) = (sin(x) * dn(y) - sin(y) * dn(x)) / (cos(x) + cos(y)) = d * dt
+    let dt = d_sin(x, y) * (sx + sy) / ((cx + cy) * (sx * dny + sy * dnx));
+    let t = d * dt;
+    let dsz = 2.0 * dt / (1.0 + t * t);
+    let (sz, cz) = (d * dsz, (1.0 - t) * (1.0 + t) / (1.0 + t * t));
+    let ez = if sz != 0.0 {
+        ell.e_sncndn(sz, cz, ell.delta(sz, cz)) / sz
+    } else {
+        1.0
+    };
+    (ez - ell.k2() * sx * sy) * dsz
+}
+
+// Divided difference of atan(x)
+fn d_atan(x: f64, y: f64) -> f64 {
+    let (d, xy) = (x - y, x * y);
+    if d == 0.0 {
+        1.0 / (1.0 + xy)
+    } else if xy.is_infinite() && xy > 0.0 {
+        0.0
+    } else if 2.0 * xy > -1.0 {
+        (d / (1.0 + xy)).atan() / d
+    } else {
+        (x.atan() - y.atan()) / d
+    }
+}
+
+// Divided difference of asinh(x)
+fn d_asinh(x: f64, y: f64) -> f64 {
+    let d = x - y;
+    let (hx, hy) = (1.0_f64.hypot(x), 1.0_f64.hypot(y));
+    if d == 0.0 {
+        1.0 / hx
+    } else if x * y > 0.0 {
+        (d * (x + y) / (x * hy + y * hx)).asinh() / d
+    } else {
+        (x * hy - y * hx).asinh() / d
+    }
+}
+
+// Divided difference of gd(x)
+fn d_gd(x: f64, y: f64) -> f64 {
+    if x.is_finite() && y.is_finite() {
+        d_atan(x.sinh(), y.sinh()) * d_sinh(x, y)
+    } else if x == y {
+        0.0
+    } else {
+        (gd(x) - gd(y)) / (x - y)
+    }
+}
+
+// Divided difference of the inverse Gudermannian in terms of x = tan(chi)
+// and y = tan(chi'), i.e. (asinh(x) - asinh(y)) / (atan(x) - atan(y))
+fn d_gdinv(x: f64, y: f64) -> f64 {
+    d_asinh(x, y) / d_atan(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InverseGeodesic;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_inverse_direct() {
+        let geod = Geodesic::wgs84();
+        let rhumb = Rhumb::new(geod);
+
+        let (s12, azi12, area) = rhumb.inverse(40.6, -73.8, 51.6, -0.5);
+        assert_relative_eq!(s12, 5771083.383328, epsilon = 1e-6);
+        assert_relative_eq!(azi12, 77.76838971026, epsilon = 1e-10);
+        assert_relative_eq!(area, 37395209100030.39, epsilon = 1e2);
+
+        let (lat2, lon2, area) = rhumb.direct(40.6, -73.8, 51.0, 5.5e6);
+        assert_relative_eq!(lat2, 71.688899882813, epsilon = 1e-11);
+        assert_relative_eq!(lon2, 0.2555198244234, epsilon = 1e-11);
+        assert_relative_eq!(area, 44095641862956.11, epsilon = 1e2);
+    }
+
+    #[test]
+    fn test_meridian_and_parallel() {
+        let geod = Geodesic::wgs84();
+        let rhumb = Rhumb::new(geod);
+
+        // Along a meridian a rhumb line is a geodesic
+        let (s12, azi12, area) = rhumb.inverse(-30.0, 10.0, 60.0, 10.0);
+        let s12_geod: f64 = geod.inverse(-30.0, 10.0, 60.0, 10.0);
+        assert_relative_eq!(s12, s12_geod, epsilon = 1e-6);
+        assert_eq!(azi12, 0.0);
+        assert_eq!(area, 0.0);
+        let (s12, _, _) = rhumb.inverse(0.0, 0.0, 90.0, 0.0);
+        let s12_geod: f64 = geod.inverse(0.0, 0.0, 90.0, 0.0);
+        assert_relative_eq!(s12, s12_geod, epsilon = 1e-6);
+
+        // Along a parallel the distance is the arc of the circle of latitude
+        let (lat, lon12) = (35.0_f64, 50.0);
+        let (sphi, cphi) = geomath::sincosd(lat);
+        let r = geod.a * cphi / (1.0 - geod._e2 * sphi * sphi).sqrt();
+        let (s12, azi12, _) = rhumb.inverse(lat, 0.0, lat, lon12);
+        assert_relative_eq!(s12, r * lon12 * DEGREE, epsilon = 1e-6);
+        assert_eq!(azi12, 90.0);
+        let (lat2, lon2, _) = rhumb.direct(lat, 0.0, 90.0, s12);
+        assert_relative_eq!(lat2, lat, epsilon = 1e-14);
+        assert_relative_eq!(lon2, lon12, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_area_along_parallel() {
+        // The area between the equator and a parallel is a band of the
+        // ellipsoid, compare with the closed form in terms of q(phi)
+        let geod = Geodesic::wgs84();
+        let rhumb = Rhumb::new(geod);
+        let e = geod._e2.sqrt();
+        let q = |phi: f64| {
+            let s = phi.sin();
+            s / (1.0 - e * e * s * s) + (e * s).atanh() / e
+        };
+        let qp = q(std::f64::consts::FRAC_PI_2);
+        for &lat in [10.0, 45.0, -70.0, 89.0].iter() {
+            let (_, _, area) = rhumb.inverse(lat, -20.0, lat, 30.0);
+            let band = geod.area() / 720.0 * 50.0 * q(lat * DEGREE) / qp;
+            assert_relative_eq!(area, band, epsilon = 1e-3, max_relative = 1e-13);
+        }
+        // A line to the pole covers the whole band
+        let (_, _, area) = rhumb.inverse(0.0, 0.0, 90.0, 30.0);
+        assert_relative_eq!(area, geod.area() / 720.0 * 30.0, max_relative = 1e-14);
+    }
+
+    #[test]
+    fn test_over_the_pole() {
+        let geod = Geodesic::wgs84();
+        let rhumb = Rhumb::new(geod);
+        let (s12, _, _) = rhumb.inverse(80.0, 0.0, 90.0, 0.0);
+        let (lat2, lon2, area) = rhumb.direct(80.0, 0.0, 0.0, 2.0 * s12);
+        assert_relative_eq!(lat2, 80.0, epsilon = 1e-10);
+        assert!(lon2.is_nan() && area.is_nan());
+    }
+
+    #[test]
+    fn test_exact() {
+        // The exact solution agrees with the series for small flattening
+        let geod = Geodesic::wgs84();
+        let rhumb = Rhumb::new_exact(geod);
+        let (s12, azi12, area) = rhumb.inverse(40.6, -73.8, 51.6, -0.5);
+        assert_relative_eq!(s12, 5771083.383328, epsilon = 1e-6);
+        assert_relative_eq!(azi12, 77.76838971026, epsilon = 1e-10);
+        assert_relative_eq!(area, 37395209100030.39, epsilon = 1e2);
+        let (lat2, lon2, area) = rhumb.direct(40.6, -73.8, 51.0, 5.5e6);
+        assert_relative_eq!(lat2, 71.688899882813, epsilon = 1e-11);
+        assert_relative_eq!(lon2, 0.2555198244234, epsilon = 1e-11);
+        assert_relative_eq!(area, 44095641862956.11, epsilon = 1e2);
+
+        // and is selected for larger flattening, where along a meridian it
+        // matches the geodesic
+        for &f in [0.1, -0.1].iter() {
+            let geod = Geodesic::new(6.4e6, f);
+            let rhumb = Rhumb::new(&geod);
+            assert!(rhumb.ell.is_some());
+            let (s12, azi12, _) = rhumb.inverse(-60.0, 10.0, 70.0, 10.0);
+            let s12_geod: f64 = geod.inverse(-60.0, 10.0, 70.0, 10.0);
+            assert_relative_eq!(s12, s12_geod, epsilon = 1e-4);
+            assert_eq!(azi12, 0.0);
+            let (lat2, lon2, _) = rhumb.direct(-60.0, 10.0, 0.0, s12);
+            assert_relative_eq!(lat2, 70.0, epsilon = 1e-11);
+            assert_eq!(lon2, 10.0);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for &f in [0.0, 1.0 / 298.257223563, -1.0 / 150.0, 0.2, -0.5].iter() {
+            let geod = Geodesic::new(6.4e6, f);
+            let rhumb = Rhumb::new(&geod);
+            let line = rhumb.line(-20.0, 170.0, 30.0);
+            for i in 1..10 {
+                let s12 = f64::from(i) * 1e6;
+                let (lat2, lon2, area) = line.position(s12);
+                let (s, azi, area1) = rhumb.inverse(-20.0, 170.0, lat2, lon2);
+                assert_relative_eq!(s, s12, epsilon = 1e-6);
+                assert_relative_eq!(azi, 30.0, epsilon = 1e-12);
+                assert_relative_eq!(area, area1, epsilon = 1e-1, max_relative = 1e-12);
+            }
+        }
+    }
+}
@@ -1,6 +1,15 @@
+//! Type-level capability flags.
+//!
+//! These mirror the runtime masks in [`capability`](crate::capability), but
+//! are selected at compile time by nesting wrappers, e.g.
+//! `Latitude<Longitude<Empty>>`. See [`Geodesic::direct_result`](crate::Geodesic::direct_result)
+//! and [`Geodesic::inverse_result`](crate::Geodesic::inverse_result).
+
 use crate::cached_weights::WeightCaps;
+use crate::geodesic_capability as mask;
 
 
+/// A set of capabilities, built from [`Empty`] and the wrapper types in this module.
 pub trait Caps {
     const LATITUDE: bool = false;
     const LONGITUDE: bool = false;
@@ -19,6 +28,17 @@ pub trait Caps {
     const C3: bool = Self::LONGITUDE;
     const C4: bool = Self::AREA;
     const CHECK_THIRD_FLATTENING: bool = false;
+
+    /// The equivalent runtime [`capability`](crate::capability) mask.
+    const MASK: u64 = (if Self::LATITUDE { mask::LATITUDE } else { mask::EMPTY })
+        | (if Self::LONGITUDE { mask::LONGITUDE } else { mask::EMPTY })
+        | (if Self::AZIMUTH { mask::AZIMUTH } else { mask::EMPTY })
+        | (if Self::DISTANCE { mask::DISTANCE } else { mask::EMPTY })
+        | (if Self::DISTANCE_IN { mask::DISTANCE_IN } else { mask::EMPTY })
+        | (if Self::REDUCEDLENGTH { mask::REDUCEDLENGTH } else { mask::EMPTY })
+        | (if Self::GEODESICSCALE { mask::GEODESICSCALE } else { mask::EMPTY })
+        | (if Self::AREA { mask::AREA } else { mask::EMPTY })
+        | (if Self::LONG_UNROLL { mask::LONG_UNROLL } else { mask::EMPTY });
 }
 impl<C: Caps> WeightCaps for C {
     const C1: bool = Self::DISTANCE | Self::DISTANCE_IN | Self::REDUCEDLENGTH | Self::GEODESICSCALE;
@@ -29,9 +49,11 @@ impl<C: Caps> WeightCaps for C {
     const CHECK_THIRD_FLATTENING: bool = false;
 }
 
+/// No capabilities.
 pub struct Empty;
 impl Caps for Empty { }
 
+/// Adds `LATITUDE` to `C`.
 pub struct Latitude<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -48,6 +70,7 @@ impl<C: Caps> Caps for Latitude<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `LONGITUDE` to `C`.
 pub struct Longitude<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -64,6 +87,7 @@ impl<C: Caps> Caps for Longitude<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `AZIMUTH` to `C`.
 pub struct Azimuth<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -80,6 +104,7 @@ impl<C: Caps> Caps for Azimuth<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `DISTANCE` to `C`.
 pub struct Distance<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -96,6 +121,7 @@ impl<C: Caps> Caps for Distance<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `DISTANCE_IN` to `C`.
 pub struct DistanceIn<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -112,6 +138,7 @@ impl<C: Caps> Caps for DistanceIn<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `REDUCEDLENGTH` to `C`.
 pub struct ReducedLength<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -128,6 +155,7 @@ impl<C: Caps> Caps for ReducedLength<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `GEODESICSCALE` to `C`.
 pub struct GeodesicScale<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -144,6 +172,7 @@ impl<C: Caps> Caps for GeodesicScale<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Adds `AREA` to `C`.
 pub struct Area<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -160,6 +189,7 @@ impl<C: Caps> Caps for Area<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Check the third flattening before use.
 pub struct CheckN<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -195,6 +225,7 @@ impl<C: Caps> Caps for ZeroChange<C> {
     const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// The direct problem is given an arc length rather than a distance.
 pub struct ArcMode<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -212,6 +243,7 @@ impl<C: Caps> Caps for ArcMode<C> {
     const ARC_MODE: bool = true | C::ARC_MODE;
 }
 
+/// Adds `LONG_UNROLL` to `C`.
 pub struct LongUnroll<C: Caps> {
     _data: std::marker::PhantomData<C>,
 }
@@ -226,11 +258,14 @@ impl<C: Caps> Caps for LongUnroll<C> {
     const AREA: bool = C::AREA;
     const CHECK_THIRD_FLATTENING: bool = C::CHECK_THIRD_FLATTENING;
     const LONG_UNROLL: bool = true | C::LONG_UNROLL;
+    const ARC_MODE: bool = C::ARC_MODE;
 }
 
+/// Matches [`capability::STANDARD`](crate::capability::STANDARD).
 #[allow(dead_code)]
 pub type Standard = Latitude<Longitude<Azimuth<Distance<Empty>>>>;
 pub type PolygonAreaCap = Latitude<Longitude<Distance<Area<Empty>>>>;
+pub type PolylineCap = Latitude<Longitude<Distance<Empty>>>;
 
 #[test]
 fn test_mask_standard() {
@@ -248,6 +283,20 @@ fn test_mask_standard() {
     assert!(<Standard as Caps>::DISTANCE);
 }
 
+#[test]
+fn test_caps_mask() {
+    use crate::geodesic_capability::{self as caps};
+
+    assert_eq!(<Empty as Caps>::MASK, caps::EMPTY);
+    assert_eq!(<Standard as Caps>::MASK, caps::STANDARD);
+    assert_eq!(<All as Caps>::MASK, caps::ALL);
+    assert_eq!(
+        <LongUnroll<ArcMode<Latitude<Empty>>> as Caps>::MASK,
+        caps::LATITUDE | caps::LONG_UNROLL
+    );
+    const _: () = assert!(<LongUnroll<ArcMode<Empty>> as Caps>::ARC_MODE);
+}
+
 #[test]
 fn test_mask_area() {
     use crate::geodesic_capability::{self as caps};
@@ -265,8 +314,20 @@ fn test_mask_area() {
     assert!(<PolygonAreaCap as Caps>::AREA);
 }
 
+#[test]
+fn test_mask_polyline() {
+    use crate::geodesic_capability::{self as caps};
+    const POLYLINE_MASK: u64 = caps::LATITUDE | caps::LONGITUDE | caps::DISTANCE;
+
+    assert_eq!((POLYLINE_MASK & caps::CAP_C4) != 0, <PolylineCap as Caps>::C4);
+    const _: () = assert!(!<PolylineCap as Caps>::C4);
+    const _: () = assert!(!<PolylineCap as Caps>::AREA);
+    const _: () = assert!(<PolylineCap as Caps>::DISTANCE);
+}
+
 
 
+/// Every capability, matching [`capability::ALL`](crate::capability::ALL).
 pub struct All;
 impl Caps for All {
     const LATITUDE: bool = true;
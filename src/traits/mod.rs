@@ -1,4 +1,6 @@
-pub(in crate) mod caps;
+//! Traits and types used to select what a calculation computes.
+
+pub mod caps;
 pub(in crate) mod lengths_return_value;
 
 pub(in crate) use self::caps::*;
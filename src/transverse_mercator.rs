@@ -0,0 +1,328 @@
+use std::f64::consts::FRAC_PI_2;
+use std::f64::consts::PI;
+use std::sync;
+
+use crate::geomath;
+use crate::internals::constants::{ALP_COEFF, BET_COEFF, GEODESIC_ORDER, WGS84_A, WGS84_F};
+use crate::internals::utils::aux_series;
+
+/// The central scale factor of the UTM projection.
+pub(in crate) const UTM_K0: f64 = 0.9996;
+
+/// The transverse Mercator projection.
+///
+/// This uses Krüger's series carried to sixth order in the third flattening
+/// `n`, as in C. F. F. Karney,
+/// [Transverse Mercator with an accuracy of a few nanometers](https://doi.org/10.1007/s00190-011-0445-3),
+/// J. Geodesy 85(8), 475&ndash;485 (2011). Within 3900 km of the central
+/// meridian the error is less than 5 nm for the WGS84 ellipsoid. The accuracy
+/// falls off further from the central meridian.
+///
+/// The central meridian `lon0` is passed to `forward` and `reverse`, and the
+/// origin of `y` is the equator. False eastings and northings are not
+/// included.
+#[derive(Debug, Clone)]
+pub struct TransverseMercator {
+    a: f64,
+    f: f64,
+    k0: f64,
+    e2: f64,
+    es: f64,
+    e2m: f64,
+    // Scale at the pole, exp(eatanhe(1, es)) * sqrt(e2m)
+    c: f64,
+    // Rectifying radius, a * b1
+    a1: f64,
+    b1: f64,
+    alp: [f64; 7],
+    bet: [f64; 7],
+}
+
+static UTM: sync::OnceLock<TransverseMercator> = sync::OnceLock::new();
+
+/// TransverseMercator maps between geographic coordinates and the transverse
+/// Mercator projection.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::TransverseMercator;
+///
+/// let tm = TransverseMercator::utm();
+///
+/// // Paris in UTM zone 31, whose central meridian is 3E
+/// let (x, y, _gamma, _k) = tm.forward(3.0, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x + 500e3, 451075.97, epsilon = 0.01);
+/// assert_relative_eq!(y, 5409142.78, epsilon = 0.01);
+///
+/// let (lat, lon, _gamma, _k) = tm.reverse(3.0, x, y);
+/// assert_relative_eq!(lat, 48.0 + 50.0 / 60.0, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 2.0 + 20.0 / 60.0, epsilon = 1e-12);
+/// ```
+impl TransverseMercator {
+    /// Create a TransverseMercator projection for the ellipsoid with
+    /// equatorial radius `a` (meters) and flattening `f`, with central scale
+    /// factor `k0`.
+    pub fn new(a: f64, f: f64, k0: f64) -> Self {
+        let n = f / (2.0 - f);
+        let e2 = f * (2.0 - f);
+        let es = f.signum() * e2.abs().sqrt();
+        let e2m = 1.0 - e2;
+        // b1 = a1/a = (1 + n^2/4 + n^4/64 + n^6/256) / (1 + n)
+        const B1_COEFF: [f64; 5] = [1.0, 4.0, 64.0, 256.0, 256.0];
+        let m = GEODESIC_ORDER / 2;
+        let b1 = geomath::polyval(m, &B1_COEFF, n * n) / (B1_COEFF[m + 1] * (1.0 + n));
+        TransverseMercator {
+            a,
+            f,
+            k0,
+            e2,
+            es,
+            e2m,
+            c: e2m.sqrt() * geomath::eatanhe(1.0, es).exp(),
+            a1: b1 * a,
+            b1,
+            alp: aux_series(n, &ALP_COEFF),
+            bet: aux_series(n, &BET_COEFF),
+        }
+    }
+
+    /// The UTM projection, the WGS84 ellipsoid with `k0` = 0.9996.
+    pub fn utm() -> &'static Self {
+        UTM.get_or_init(|| TransverseMercator::new(WGS84_A, WGS84_F, UTM_K0))
+    }
+
+    /// The equatorial radius of the ellipsoid (meters).
+    pub fn equatorial_radius(&self) -> f64 {
+        self.a
+    }
+
+    /// The flattening of the ellipsoid.
+    pub fn flattening(&self) -> f64 {
+        self.f
+    }
+
+    /// The central scale factor.
+    pub fn central_scale(&self) -> f64 {
+        self.k0
+    }
+
+    /// Project (`lat`, `lon`) with central meridian `lon0`.
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn forward(&self, lon0: f64, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let lat = geomath::lat_fix(lat);
+        let (lon, _) = geomath::ang_diff(lon0, lon);
+        // Explicitly enforce the parity
+        let mut latsign = if lat.is_sign_negative() { -1.0 } else { 1.0 };
+        let lonsign = if lon.is_sign_negative() { -1.0 } else { 1.0 };
+        let mut lon = lon * lonsign;
+        let lat = lat * latsign;
+        let backside = lon > 90.0;
+        if backside {
+            if lat == 0.0 {
+                latsign = -1.0;
+            }
+            lon = 180.0 - lon;
+        }
+        let (sphi, cphi) = geomath::sincosd(lat);
+        let (slam, clam) = geomath::sincosd(lon);
+        let (xip, etap, mut gamma, mut k) = if lat != 90.0 {
+            let tau = sphi / cphi;
+            let taup = geomath::taupf(tau, self.es);
+            (
+                taup.atan2(clam),
+                (slam / taup.hypot(clam)).asinh(),
+                geomath::atan2d(slam * taup, clam * 1.0_f64.hypot(taup)),
+                (self.e2m + self.e2 * cphi * cphi).sqrt() * 1.0_f64.hypot(tau) / taup.hypot(clam),
+            )
+        } else {
+            (FRAC_PI_2, 0.0, lon, self.c)
+        };
+        // Map the Gauss-Schreiber TM to the Gauss-Krüger TM, including the
+        // change in convergence and scale
+        let ((xi, eta), (zr, zi)) = clenshaw(&self.alp, 1.0, xip, etap);
+        gamma -= geomath::atan2d(zi, zr);
+        k *= self.b1 * zr.hypot(zi);
+        let y = self.a1 * self.k0 * (if backside { PI - xi } else { xi }) * latsign;
+        let x = self.a1 * self.k0 * eta * lonsign;
+        if backside {
+            gamma = 180.0 - gamma;
+        }
+        gamma = geomath::ang_normalize(gamma * latsign * lonsign);
+        (x, y, gamma, k * self.k0)
+    }
+
+    /// Find the point which (`x`, `y`) with central meridian `lon0` came
+    /// from.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees), in the range [-180, 180].
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn reverse(&self, lon0: f64, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        let xi = y / (self.a1 * self.k0);
+        let eta = x / (self.a1 * self.k0);
+        // Explicitly enforce the parity
+        let xisign = if xi.is_sign_negative() { -1.0 } else { 1.0 };
+        let etasign = if eta.is_sign_negative() { -1.0 } else { 1.0 };
+        let mut xi = xi * xisign;
+        let eta = eta * etasign;
+        let backside = xi > FRAC_PI_2;
+        if backside {
+            xi = PI - xi;
+        }
+        // Map the Gauss-Krüger TM to the Gauss-Schreiber TM
+        let ((xip, etap), (zr, zi)) = clenshaw(&self.bet, -1.0, xi, eta);
+        let mut gamma = geomath::atan2d(zi, zr);
+        let mut k = self.b1 / zr.hypot(zi);
+        let s = etap.sinh();
+        // cos(pi/2) might be negative
+        let c = xip.cos().max(0.0);
+        let r = s.hypot(c);
+        let (lat, mut lon) = if r != 0.0 {
+            let sxip = xip.sin();
+            let tau = geomath::tauf(sxip / r, self.es);
+            gamma += geomath::atan2d(sxip * etap.tanh(), c);
+            // Note cos(phi') * cosh(eta') = r
+            k *= (self.e2m + self.e2 / (1.0 + tau * tau)).sqrt() * 1.0_f64.hypot(tau) * r;
+            (tau.atan().to_degrees(), geomath::atan2d(s, c))
+        } else {
+            k *= self.c;
+            (90.0, 0.0)
+        };
+        if backside {
+            lon = 180.0 - lon;
+        }
+        lon = geomath::ang_normalize(lon * etasign + lon0);
+        if backside {
+            gamma = 180.0 - gamma;
+        }
+        gamma = geomath::ang_normalize(gamma * xisign * etasign);
+        (lat * xisign, lon, gamma, k * self.k0)
+    }
+}
+
+// Evaluate zeta + sign * sum(c[j] * sin(2*j*zeta)) and its derivative with
+// respect to zeta = xi + i*eta using Clenshaw summation, returning the pairs
+// of real and imaginary parts.
+fn clenshaw(c: &[f64; 7], sign: f64, xi: f64, eta: f64) -> ((f64, f64), (f64, f64)) {
+    let (s0, c0) = (2.0 * xi).sin_cos();
+    let (sh0, ch0) = ((2.0 * eta).sinh(), (2.0 * eta).cosh());
+    // 2 * cos(2*zeta)
+    let (ar, ai) = (2.0 * c0 * ch0, -2.0 * s0 * sh0);
+    let mut n = GEODESIC_ORDER;
+    let (mut y0, mut y1, mut z0, mut z1) = ((0.0, 0.0), (0.0, 0.0), (0.0, 0.0), (0.0, 0.0));
+    if n % 2 == 1 {
+        y0 = (sign * c[n], 0.0);
+        z0 = (sign * 2.0 * n as f64 * c[n], 0.0);
+        n -= 1;
+    }
+    // y = a * y0 - y1 + d
+    let step = |y0: (f64, f64), y1: (f64, f64), d: f64| {
+        (ar * y0.0 - ai * y0.1 - y1.0 + d, ar * y0.1 + ai * y0.0 - y1.1)
+    };
+    while n > 0 {
+        y1 = step(y0, y1, sign * c[n]);
+        z1 = step(z0, z1, sign * 2.0 * n as f64 * c[n]);
+        n -= 1;
+        y0 = step(y1, y0, sign * c[n]);
+        z0 = step(z1, z0, sign * 2.0 * n as f64 * c[n]);
+        n -= 1;
+    }
+    // cos(2*zeta)
+    let (ar, ai) = (ar / 2.0, ai / 2.0);
+    let z = (1.0 - z1.0 + ar * z0.0 - ai * z0.1, -z1.1 + ar * z0.1 + ai * z0.0);
+    // sin(2*zeta)
+    let (ar, ai) = (s0 * ch0, c0 * sh0);
+    let y = (xi + ar * y0.0 - ai * y0.1, eta + ar * y0.1 + ai * y0.0);
+    (y, z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        // Reference values from the conformal map of the meridian distance
+        // evaluated with complex latitudes in multiprecision arithmetic
+        let tm = TransverseMercator::utm();
+        let cases = [
+            (
+                (48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0),
+                (-48924.02748353562, 5409142.780163737, -0.50187522736432826, 0.99962940478946573),
+            ),
+            (
+                (40.0, 13.0),
+                (854342.8422472186, 4475948.549427395, 6.4668123657984894, 1.0085980541466780),
+            ),
+            (
+                (-30.0, -22.0),
+                (-2449238.710668323, -3597203.170832872, 13.134211835208889, 1.0745041450131439),
+            ),
+        ];
+        for &((lat, lon), (x0, y0, gamma0, k0)) in cases.iter() {
+            let (x, y, gamma, k) = tm.forward(3.0, lat, lon);
+            assert_relative_eq!(x, x0, epsilon = 1e-8);
+            assert_relative_eq!(y, y0, epsilon = 1e-8);
+            assert_relative_eq!(gamma, gamma0, epsilon = 1e-13);
+            assert_relative_eq!(k, k0, epsilon = 1e-14);
+
+            let (lat1, lon1, gamma1, k1) = tm.reverse(3.0, x, y);
+            assert_relative_eq!(lat1, lat, epsilon = 1e-13);
+            assert_relative_eq!(lon1, lon, epsilon = 1e-13);
+            assert_relative_eq!(gamma1, gamma, epsilon = 1e-13);
+            assert_relative_eq!(k1, k, epsilon = 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_special_points() {
+        let tm = TransverseMercator::utm();
+        // The central meridian is mapped to the meridian distance times k0
+        let (x, y, gamma, k) = tm.forward(-75.0, 0.0, -75.0);
+        assert_eq!((x, y, gamma), (0.0, 0.0, 0.0));
+        assert_relative_eq!(k, UTM_K0, epsilon = 1e-15);
+        let (x, y, gamma, k) = tm.forward(-75.0, 90.0, 0.0);
+        assert_eq!(x, 0.0);
+        assert_relative_eq!(y, UTM_K0 * 10001965.729312723, epsilon = 1e-8);
+        assert_relative_eq!(gamma, 75.0, epsilon = 1e-13);
+        assert_relative_eq!(k, UTM_K0, epsilon = 1e-15);
+        let (lat, _, _, _) = tm.reverse(-75.0, 0.0, y);
+        assert_relative_eq!(lat, 90.0, epsilon = 1e-13);
+
+        // Symmetry about the central meridian and the equator
+        let (x, y, gamma, k) = tm.forward(10.0, 35.0, 14.0);
+        let (x1, y1, gamma1, k1) = tm.forward(10.0, -35.0, 6.0);
+        assert_eq!((x1, y1, gamma1, k1), (-x, -y, gamma, k));
+    }
+
+    #[test]
+    fn test_other_ellipsoids() {
+        // Round trips on a sphere and on oblate and prolate ellipsoids
+        for &f in [0.0, 1.0 / 150.0, -1.0 / 150.0].iter() {
+            let tm = TransverseMercator::new(6.4e6, f, 1.0);
+            for &(lat, lon) in [(0.0, 30.0), (45.0, 20.0), (-80.0, 60.0), (10.0, 5.0)].iter() {
+                let (x, y, gamma, k) = tm.forward(0.0, lat, lon);
+                let (lat1, lon1, gamma1, k1) = tm.reverse(0.0, x, y);
+                assert_relative_eq!(lat1, lat, epsilon = 1e-12);
+                assert_relative_eq!(lon1, lon, epsilon = 1e-12);
+                assert_relative_eq!(gamma1, gamma, epsilon = 1e-12);
+                assert_relative_eq!(k1, k, epsilon = 1e-12);
+            }
+        }
+        // On a sphere k = 1/sqrt(1 - (cos(lat) sin(lon))^2)
+        let tm = TransverseMercator::new(6.4e6, 0.0, 1.0);
+        let (_, _, _, k) = tm.forward(0.0, 45.0, 20.0);
+        let b = 45.0_f64.to_radians().cos() * 20.0_f64.to_radians().sin();
+        assert_relative_eq!(k, 1.0 / (1.0 - b * b).sqrt(), epsilon = 1e-15);
+    }
+}
@@ -0,0 +1,565 @@
+use std::f64::consts::PI;
+use std::sync;
+
+use crate::geomath::{self, EllipticFunction};
+use crate::internals::constants::{WGS84_A, WGS84_F};
+use crate::transverse_mercator::UTM_K0;
+
+// Newton iterations in zetainv and sigmainv
+const NUMIT: usize = 10;
+
+/// The exact transverse Mercator projection.
+///
+/// This follows L. P. Lee,
+/// [Conformal Projections Based on Jacobian Elliptic Functions](https://doi.org/10.3138/X687-1574-4325-WM62),
+/// Part V of Conformal Projections Based on Elliptic Functions, Cartographica
+/// 13, Monograph 16 (1976), with the improvements described in C. F. F.
+/// Karney, [Transverse Mercator with an accuracy of a few nanometers](https://doi.org/10.1007/s00190-011-0445-3),
+/// J. Geodesy 85(8), 475&ndash;485 (2011). The projection is accurate to
+/// roundoff everywhere within 90&deg; of the central meridian, at the cost of
+/// being several times slower than [`TransverseMercator`](crate::TransverseMercator).
+///
+/// The flattening `f` must be positive. The central meridian `lon0` is passed
+/// to `forward` and `reverse`, and the origin of `y` is the equator. False
+/// eastings and northings are not included.
+#[derive(Debug, Clone)]
+pub struct TransverseMercatorExact {
+    a: f64,
+    f: f64,
+    k0: f64,
+    // e^2 and 1 - e^2, the parameters of the elliptic functions of u and v
+    mu: f64,
+    mv: f64,
+    e: f64,
+    eu: EllipticFunction,
+    ev: EllipticFunction,
+}
+
+static UTM: sync::OnceLock<TransverseMercatorExact> = sync::OnceLock::new();
+
+/// TransverseMercatorExact maps between geographic coordinates and the
+/// transverse Mercator projection.
+///
+/// # Example
+/// ```rust
+/// use geographiclib_rs::{TransverseMercator, TransverseMercatorExact};
+///
+/// let tm = TransverseMercatorExact::utm();
+///
+/// // Near the central meridian the series and the exact projection agree
+/// let (x, y, _gamma, _k) = tm.forward(3.0, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+/// let (x1, y1, _gamma1, _k1) = TransverseMercator::utm().forward(3.0, 48.0 + 50.0 / 60.0, 2.0 + 20.0 / 60.0);
+///
+/// use approx::assert_relative_eq;
+/// assert_relative_eq!(x, x1, epsilon = 1e-6);
+/// assert_relative_eq!(y, y1, epsilon = 1e-6);
+///
+/// // But the exact projection also works 70 degrees from it
+/// let (x, y, _gamma, _k) = tm.forward(0.0, 75.0, 70.0);
+/// let (lat, lon, _gamma, _k) = tm.reverse(0.0, x, y);
+/// assert_relative_eq!(lat, 75.0, epsilon = 1e-12);
+/// assert_relative_eq!(lon, 70.0, epsilon = 1e-12);
+/// ```
+impl TransverseMercatorExact {
+    /// Create a TransverseMercatorExact projection for the ellipsoid with
+    /// equatorial radius `a` (meters) and positive flattening `f`, with
+    /// central scale factor `k0`.
+    ///
+    /// # Panics
+    /// Panics unless `a` and `k0` are positive and finite and `f` is in
+    /// (0, 1), i.e. the ellipsoid is oblate.
+    pub fn new(a: f64, f: f64, k0: f64) -> Self {
+        assert!(
+            a.is_finite() && a > 0.0,
+            "TransverseMercatorExact::new: equatorial radius {} is not positive",
+            a
+        );
+        assert!(f > 0.0, "TransverseMercatorExact::new: flattening {} is not positive", f);
+        assert!(f < 1.0, "TransverseMercatorExact::new: polar semi-axis is not positive, f = {}", f);
+        assert!(k0.is_finite() && k0 > 0.0, "TransverseMercatorExact::new: scale {} is not positive", k0);
+        let mu = f * (2.0 - f);
+        let mv = 1.0 - mu;
+        TransverseMercatorExact {
+            a,
+            f,
+            k0,
+            mu,
+            mv,
+            e: mu.sqrt(),
+            eu: EllipticFunction::new(mu),
+            ev: EllipticFunction::new(mv),
+        }
+    }
+
+    /// The exact UTM projection, the WGS84 ellipsoid with `k0` = 0.9996.
+    pub fn utm() -> &'static Self {
+        UTM.get_or_init(|| TransverseMercatorExact::new(WGS84_A, WGS84_F, UTM_K0))
+    }
+
+    /// The equatorial radius of the ellipsoid (meters).
+    pub fn equatorial_radius(&self) -> f64 {
+        self.a
+    }
+
+    /// The flattening of the ellipsoid.
+    pub fn flattening(&self) -> f64 {
+        self.f
+    }
+
+    /// The central scale factor.
+    pub fn central_scale(&self) -> f64 {
+        self.k0
+    }
+
+    /// Project (`lat`, `lon`) with central meridian `lon0`.
+    ///
+    /// # Returns
+    /// - x easting of point (meters).
+    /// - y northing of point (meters).
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn forward(&self, lon0: f64, lat: f64, lon: f64) -> (f64, f64, f64, f64) {
+        let lat = geomath::lat_fix(lat);
+        let (lon, _) = geomath::ang_diff(lon0, lon);
+        // Explicitly enforce the parity
+        let mut latsign = if lat.is_sign_negative() { -1.0 } else { 1.0 };
+        let lonsign = if lon.is_sign_negative() { -1.0 } else { 1.0 };
+        let mut lon = lon * lonsign;
+        let lat = lat * latsign;
+        let backside = lon > 90.0;
+        if backside {
+            if lat == 0.0 {
+                latsign = -1.0;
+            }
+            lon = 180.0 - lon;
+        }
+        let lam = lon.to_radians();
+        let (slat, clat) = geomath::sincosd(lat);
+        let tau = slat / clat;
+
+        // u, v = coordinates for the Thompson TM, Lee 54
+        let (u, v) = if lat == 90.0 {
+            (self.eu.k(), 0.0)
+        } else if lat == 0.0 && lon == 90.0 * (1.0 - self.e) {
+            (0.0, self.ev.k())
+        } else {
+            // tau = tan(phi), taup = sinh(psi)
+            self.zetainv(geomath::taupf(tau, self.e), lam)
+        };
+        let (snu, cnu, dnu) = self.eu.sncndn(u);
+        let (snv, cnv, dnv) = self.ev.sncndn(v);
+
+        let (mut xi, eta) = self.sigma(v, (snu, cnu, dnu), (snv, cnv, dnv));
+        if backside {
+            xi = 2.0 * self.eu.e() - xi;
+        }
+        let y = xi * self.a * self.k0 * latsign;
+        let x = eta * self.a * self.k0 * lonsign;
+
+        let (mut gamma, k) = if lat == 90.0 {
+            (lon, 1.0)
+        } else {
+            // Recompute (tau, lam) from (u, v) to improve the accuracy of
+            // the scale
+            let (taup, _) = self.zeta((snu, cnu, dnu), (snv, cnv, dnv));
+            let tau = geomath::tauf(taup, self.e);
+            let (gamma, k) = self.scale(tau, (snu, cnu, dnu), (snv, cnv, dnv));
+            (gamma.to_degrees(), k)
+        };
+        if backside {
+            gamma = 180.0 - gamma;
+        }
+        (x, y, gamma * latsign * lonsign, k * self.k0)
+    }
+
+    /// Find (`lat`, `lon`) for the point (`x`, `y`) projected with central
+    /// meridian `lon0`.
+    ///
+    /// # Returns
+    /// - lat latitude of point (degrees).
+    /// - lon longitude of point (degrees).
+    /// - gamma meridian convergence at point (degrees).
+    /// - k scale of projection at point.
+    pub fn reverse(&self, lon0: f64, x: f64, y: f64) -> (f64, f64, f64, f64) {
+        // This undoes the steps in forward
+        let xi = y / (self.a * self.k0);
+        let eta = x / (self.a * self.k0);
+        // Explicitly enforce the parity
+        let xisign = if xi.is_sign_negative() { -1.0 } else { 1.0 };
+        let etasign = if eta.is_sign_negative() { -1.0 } else { 1.0 };
+        let mut xi = xi * xisign;
+        let eta = eta * etasign;
+        let backside = xi > self.eu.e();
+        if backside {
+            xi = 2.0 * self.eu.e() - xi;
+        }
+
+        // u, v = coordinates for the Thompson TM, Lee 54
+        let (u, v) = if xi == 0.0 && eta == self.ev.ke() {
+            (0.0, self.ev.k())
+        } else {
+            self.sigmainv(xi, eta)
+        };
+        let (snu, cnu, dnu) = self.eu.sncndn(u);
+        let (snv, cnv, dnv) = self.ev.sncndn(v);
+        let (lat, mut lon, mut gamma, k) = if v != 0.0 || u != self.eu.k() {
+            let (taup, lam) = self.zeta((snu, cnu, dnu), (snv, cnv, dnv));
+            let tau = geomath::tauf(taup, self.e);
+            let (gamma, k) = self.scale(tau, (snu, cnu, dnu), (snv, cnv, dnv));
+            (tau.atan().to_degrees(), lam.to_degrees(), gamma.to_degrees(), k)
+        } else {
+            (90.0, 0.0, 0.0, 1.0)
+        };
+
+        if backside {
+            lon = 180.0 - lon;
+            gamma = 180.0 - gamma;
+        }
+        let lon = geomath::ang_normalize(lon * etasign + lon0);
+        (lat * xisign, lon, gamma * xisign * etasign, k * self.k0)
+    }
+
+    // The isometric coordinates (taup = sinh(psi), lam) in terms of the
+    // Jacobi elliptic functions of u and v, Lee 54.17
+    fn zeta(&self, (snu, cnu, dnu): (f64, f64, f64), (snv, cnv, dnv): (f64, f64, f64)) -> (f64, f64) {
+        // Write
+        // atanh(snu * dnv) = asinh(snu * dnv / sqrt(cnu^2 + mv * snu^2 * snv^2))
+        // atanh(e * snu / dnv) = asinh(e * snu / sqrt(mu * cnu^2 + mv * cnv^2))
+        // Overflow value s.t. atan(overflow) = pi/2
+        let overflow = 1.0 / (f64::EPSILON * f64::EPSILON);
+        let d1 = (cnu * cnu + self.mv * (snu * snv).powi(2)).sqrt();
+        let d2 = (self.mu * cnu * cnu + self.mv * cnv * cnv).sqrt();
+        let t1 = if d1 != 0.0 {
+            snu * dnv / d1
+        } else {
+            overflow.copysign(snu)
+        };
+        let t2 = if d2 != 0.0 {
+            (self.e * (self.e * snu / d2).asinh()).sinh()
+        } else {
+            overflow.copysign(snu)
+        };
+        // psi = asinh(t1) - asinh(t2)
+        // taup = sinh(psi)
+        let taup = t1 * 1.0_f64.hypot(t2) - t2 * 1.0_f64.hypot(t1);
+        let lam = if d1 != 0.0 && d2 != 0.0 {
+            (dnu * snv).atan2(cnu * cnv) - self.e * (self.e * cnu * snv).atan2(dnu * cnv)
+        } else {
+            0.0
+        };
+        (taup, lam)
+    }
+
+    // The derivative dw/dzeta, Lee 54.21
+    fn dwdzeta(&self, (snu, cnu, dnu): (f64, f64, f64), (snv, cnv, dnv): (f64, f64, f64)) -> (f64, f64) {
+        // Write (1 - dnu^2 * snv^2) = (cnv^2 + mu * snu^2 * snv^2), see A+S
+        // 16.21.4
+        let d = self.mv * (cnv * cnv + self.mu * (snu * snv).powi(2)).powi(2);
+        let du = cnu * dnu * dnv * (cnv * cnv - self.mu * (snu * snv).powi(2)) / d;
+        let dv = -snu * snv * cnv * ((dnu * dnv).powi(2) + self.mu * cnu * cnu) / d;
+        (du, dv)
+    }
+
+    // Starting point for zetainv, also returning whether it is accurate
+    // enough to skip Newton's method
+    fn zetainv0(&self, psi: f64, lam: f64) -> (f64, f64, bool) {
+        let e = self.e;
+        let taytol = f64::EPSILON.powf(0.6);
+        if psi < -e * PI / 4.0 && lam > (1.0 - 2.0 * e) * PI / 2.0 && psi < lam - (1.0 - e) * PI / 2.0 {
+            // N.B. this branch is normally not taken because psi < 0 is
+            // converted to psi > 0 by forward.
+            //
+            // There's a log singularity at w = w0 = Eu.K() + i * Ev.K(),
+            // corresponding to the south pole, where we have, approximately
+            //
+            //   psi = e + i * pi/2 - e * atanh(cos(i * (w - w0)/(1 + mu/2)))
+            //
+            // Inverting this gives:
+            let psix = 1.0 - psi / e;
+            let lamx = (PI / 2.0 - lam) / e;
+            let u = (lamx.sin() / lamx.cos().hypot(psix.sinh())).asinh() * (1.0 + self.mu / 2.0);
+            let v = lamx.cos().atan2(psix.sinh()) * (1.0 + self.mu / 2.0);
+            (self.eu.k() - u, self.ev.k() - v, false)
+        } else if psi < e * PI / 2.0 && lam > (1.0 - 2.0 * e) * PI / 2.0 {
+            // At w = w0 = i * Ev.K(), we have
+            //
+            //     zeta = zeta0 = i * (1 - e) * pi/2
+            //     zeta' = zeta'' = 0
+            //
+            // including the next term in the Taylor series gives:
+            //
+            //     zeta = zeta0 - (mv * e) / 3 * (w - w0)^3
+            //
+            // When inverting this, we map arg(w - w0) = [-90, 0] to
+            // arg(zeta - zeta0) = [-90, 180]
+            let dlam = lam - (1.0 - e) * PI / 2.0;
+            let rad = psi.hypot(dlam);
+            // atan2(dlam - psi, psi + dlam) + 45d gives arg(zeta - zeta0) in
+            // the range [-135, 225). Subtracting 180 (since the multiplier is
+            // negative) makes the range [-315, 45). Multiplying by 1/3 (for
+            // the cube root) gives the range [-105, 15). In particular the
+            // range [-90, 180] in zeta space maps to [-90, 0] in w space as
+            // required.
+            let ang = (dlam - psi).atan2(psi + dlam) - 0.75 * PI;
+            // Error using this guess is about 0.21 * (rad/e)^(5/3)
+            let done = rad < e * taytol;
+            let rad = (3.0 / (self.mv * e) * rad).cbrt();
+            let ang = ang / 3.0;
+            (rad * ang.cos(), rad * ang.sin() + self.ev.k(), done)
+        } else {
+            // Use the spherical TM, Lee 12.6, writing
+            // atanh(sin(lam) / cosh(psi)) = asinh(sin(lam) / hypot(cos(lam), sinh(psi))).
+            // This takes care of the log singularity at zeta = Eu.K()
+            // (corresponding to the north pole)
+            let v = (lam.sin() / lam.cos().hypot(psi.sinh())).asinh();
+            let u = psi.sinh().atan2(lam.cos());
+            // But scale to put 90,0 on the right place
+            let scale = self.eu.k() / (PI / 2.0);
+            (u * scale, v * scale, false)
+        }
+    }
+
+    // Invert zeta using Newton's method
+    fn zetainv(&self, taup: f64, lam: f64) -> (f64, f64) {
+        let psi = taup.asinh();
+        let scal = 1.0 / 1.0_f64.hypot(taup);
+        let (mut u, mut v, done) = self.zetainv0(psi, lam);
+        if done {
+            return (u, v);
+        }
+        let stol2 = 0.1 * f64::EPSILON / psi.max(1.0).powi(2);
+        // min iterations = 2, max iterations = 6; mean = 4.0
+        let mut trip = false;
+        for _ in 0..NUMIT {
+            let snu = self.eu.sncndn(u);
+            let snv = self.ev.sncndn(v);
+            let (tau1, lam1) = self.zeta(snu, snv);
+            let (du1, dv1) = self.dwdzeta(snu, snv);
+            let tau1 = (tau1 - taup) * scal;
+            let lam1 = lam1 - lam;
+            let delu = tau1 * du1 - lam1 * dv1;
+            let delv = tau1 * dv1 + lam1 * du1;
+            u -= delu;
+            v -= delv;
+            if trip {
+                break;
+            }
+            let delw2 = delu * delu + delv * delv;
+            trip = delw2 < stol2 || delw2.is_nan();
+        }
+        (u, v)
+    }
+
+    // The transverse Mercator coordinates (xi, eta), divided by a, in terms
+    // of v and the Jacobi elliptic functions of u and v, Lee 55.4
+    fn sigma(&self, v: f64, (snu, cnu, dnu): (f64, f64, f64), (snv, cnv, dnv): (f64, f64, f64)) -> (f64, f64) {
+        // Write dnu^2 + dnv^2 - 1 = mu * cnu^2 + mv * cnv^2
+        let d = self.mu * cnu * cnu + self.mv * cnv * cnv;
+        let xi = self.eu.e_sncndn(snu, cnu, dnu) - self.mu * snu * cnu * dnu / d;
+        let eta = v - self.ev.e_sncndn(snv, cnv, dnv) + self.mv * snv * cnv * dnv / d;
+        (xi, eta)
+    }
+
+    // The derivative dw/dsigma, the reciprocal of Lee 55.9
+    fn dwdsigma(&self, (snu, cnu, dnu): (f64, f64, f64), (snv, cnv, dnv): (f64, f64, f64)) -> (f64, f64) {
+        // dw/ds = dn(w)^2/mv, expanding complex dn(w) using A+S 16.21.4
+        let d = self.mv * (cnv * cnv + self.mu * (snu * snv).powi(2)).powi(2);
+        let dnr = dnu * cnv * dnv;
+        let dni = -self.mu * snu * cnu * snv;
+        ((dnr * dnr - dni * dni) / d, 2.0 * dnr * dni / d)
+    }
+
+    // Starting point for sigmainv, also returning whether it is accurate
+    // enough to skip Newton's method
+    fn sigmainv0(&self, xi: f64, eta: f64) -> (f64, f64, bool) {
+        let taytol = f64::EPSILON.powf(0.6);
+        let (ke, e) = (self.ev.ke(), self.eu.e());
+        if eta > 1.25 * ke || (xi < -0.25 * e && xi < eta - ke) {
+            // sigma has a simple pole at w = w0 = Eu.K() + i * Ev.K() and
+            // sigma is approximated by
+            //
+            //     sigma = (Eu.E() + i * Ev.KE()) + 1/(w - w0)
+            let x = xi - e;
+            let y = eta - ke;
+            let r2 = x * x + y * y;
+            (self.eu.k() + x / r2, self.ev.k() - y / r2, false)
+        } else if (eta > 0.75 * ke && xi < 0.25 * e) || eta > ke {
+            // At w = w0 = i * Ev.K(), we have
+            //
+            //     sigma = sigma0 = i * Ev.KE()
+            //     sigma' = sigma'' = 0
+            //
+            // including the next term in the Taylor series gives:
+            //
+            //     sigma = sigma0 - mv / 3 * (w - w0)^3
+            //
+            // When inverting this, we map arg(w - w0) = [-pi/2, -pi/6] to
+            // arg(sigma - sigma0) = [-pi/2, pi/2]
+            let deta = eta - ke;
+            let rad = xi.hypot(deta);
+            // Map the range [-90, 180] in sigma space to [-90, 0] in w space.
+            // See the discussion in zetainv0 on the cut for ang.
+            let ang = (deta - xi).atan2(xi + deta) - 0.75 * PI;
+            // Error using this guess is about 0.068 * rad^(5/3)
+            let done = rad < 2.0 * taytol;
+            let rad = (3.0 / self.mv * rad).cbrt();
+            let ang = ang / 3.0;
+            (rad * ang.cos(), rad * ang.sin() + self.ev.k(), done)
+        } else {
+            // Else use w = sigma * Eu.K/Eu.E (which is correct in the limit
+            // e -> 0)
+            let scale = self.eu.k() / e;
+            (xi * scale, eta * scale, false)
+        }
+    }
+
+    // Invert sigma using Newton's method
+    fn sigmainv(&self, xi: f64, eta: f64) -> (f64, f64) {
+        let (mut u, mut v, done) = self.sigmainv0(xi, eta);
+        if done {
+            return (u, v);
+        }
+        let tol2 = 0.1 * f64::EPSILON;
+        // min iterations = 2, max iterations = 7; mean = 3.9
+        let mut trip = false;
+        for _ in 0..NUMIT {
+            let snu = self.eu.sncndn(u);
+            let snv = self.ev.sncndn(v);
+            let (xi1, eta1) = self.sigma(v, snu, snv);
+            let (du1, dv1) = self.dwdsigma(snu, snv);
+            let xi1 = xi1 - xi;
+            let eta1 = eta1 - eta;
+            let delu = xi1 * du1 - eta1 * dv1;
+            let delv = xi1 * dv1 + eta1 * du1;
+            u -= delu;
+            v -= delv;
+            if trip {
+                break;
+            }
+            let delw2 = delu * delu + delv * delv;
+            trip = delw2 < tol2 || delw2.is_nan();
+        }
+        (u, v)
+    }
+
+    // The meridian convergence (radians) and scale, Lee 55.12 and 55.13
+    fn scale(&self, tau: f64, (snu, cnu, dnu): (f64, f64, f64), (snv, cnv, dnv): (f64, f64, f64)) -> (f64, f64) {
+        // sec(phi)^2
+        let sec2 = 1.0 + tau * tau;
+        // Lee 55.12 negated for our sign convention. gamma gives the bearing
+        // (clockwise from true north) of grid north
+        let gamma = (self.mv * snu * snv * cnv).atan2(cnu * dnu * dnv);
+        // Lee 55.13 with nu given by Lee 9.1. In the sqrt change the
+        // numerator from (1 - snu^2 * dnv^2) to (mv * snv^2 + cnu^2 * dnv^2)
+        // to maintain accuracy near phi = 90, and change the denominator from
+        // (dnu^2 + dnv^2 - 1) to (mu * cnu^2 + mv * cnv^2) to maintain
+        // accuracy near phi = 0, lam = 90 * (1 - e).
+        let k = (self.mv + self.mu / sec2).sqrt()
+            * sec2.sqrt()
+            * ((self.mv * snv * snv + (cnu * dnv).powi(2)) / (self.mu * cnu * cnu + self.mv * cnv * cnv)).sqrt();
+        (gamma, k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TransverseMercator;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_forward_reverse() {
+        // Reference values from the conformal map of the meridian distance
+        // evaluated with complex latitudes in multiprecision arithmetic
+        let tm = TransverseMercatorExact::utm();
+        let cases = [
+            ((40.0, 10.0), (854342.8422472186, 4475948.549427395, 6.4668123657984894, 1.0085980541466780)),
+            ((75.0, 70.0), (1587271.6915491686, 9413487.594986271, 69.353639687121957, 1.0305325587594800)),
+            ((10.0, 80.0), (13309920.758442870, 5200439.520976653, 47.485863498772845, 4.0859930310933073)),
+            ((-60.0, 89.0), (3509895.8324021192, -9933581.894372697, -88.845983810689451, 1.1538572748618095)),
+        ];
+        for &((lat, lon), (x0, y0, gamma0, k0)) in cases.iter() {
+            let (x, y, gamma, k) = tm.forward(0.0, lat, lon);
+            assert_relative_eq!(x, x0, epsilon = 1e-7);
+            assert_relative_eq!(y, y0, epsilon = 1e-7);
+            assert_relative_eq!(gamma, gamma0, epsilon = 1e-12);
+            assert_relative_eq!(k, k0, epsilon = 1e-13);
+
+            let (lat1, lon1, gamma1, k1) = tm.reverse(0.0, x, y);
+            assert_relative_eq!(lat1, lat, epsilon = 1e-12);
+            assert_relative_eq!(lon1, lon, epsilon = 1e-12);
+            assert_relative_eq!(gamma1, gamma, epsilon = 1e-12);
+            assert_relative_eq!(k1, k, epsilon = 1e-13);
+        }
+    }
+
+    #[test]
+    fn test_series_agreement() {
+        // Within 3900 km of the central meridian the series is accurate to
+        // a few nanometers
+        let exact = TransverseMercatorExact::utm();
+        let series = TransverseMercator::utm();
+        for &(lat, lon) in [(0.0, 1.0), (30.0, 5.0), (-45.0, -20.0), (60.0, 40.0), (89.0, 150.0)].iter() {
+            let (x, y, gamma, k) = exact.forward(-3.0, lat, lon - 3.0);
+            let (x1, y1, gamma1, k1) = series.forward(-3.0, lat, lon - 3.0);
+            assert_relative_eq!(x, x1, epsilon = 1e-8);
+            assert_relative_eq!(y, y1, epsilon = 1e-8);
+            assert_relative_eq!(gamma, gamma1, epsilon = 1e-12);
+            assert_relative_eq!(k, k1, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_special_points() {
+        let tm = TransverseMercatorExact::utm();
+        let e = tm.mu.sqrt();
+        // The pole
+        let (x, y, gamma, k) = tm.forward(0.0, 90.0, 30.0);
+        assert_eq!(x, 0.0);
+        assert_relative_eq!(y, UTM_K0 * 10001965.729312723, epsilon = 1e-8);
+        assert_eq!(gamma, 30.0);
+        assert_eq!(k, UTM_K0);
+        assert_relative_eq!(tm.reverse(0.0, x, y).0, 90.0, epsilon = 1e-13);
+        // The branch point on the equator, 90 * (1 - e) from the central
+        // meridian, where the scale is k0 / e
+        let (x, y, _, k) = tm.forward(0.0, 0.0, 90.0 * (1.0 - e));
+        assert_eq!(y, 0.0);
+        assert_relative_eq!(x, UTM_K0 * WGS84_A * tm.ev.ke(), epsilon = 1e-8);
+        assert_relative_eq!(k, UTM_K0 / e, epsilon = 1e-12);
+        let (lat, lon, _, _) = tm.reverse(0.0, x, 0.0);
+        assert_eq!(lat, 0.0);
+        assert_relative_eq!(lon, 90.0 * (1.0 - e), epsilon = 1e-12);
+        // The backside of the projection
+        let (x, y, gamma, k) = tm.forward(0.0, 50.0, 120.0);
+        let (lat, lon, gamma1, k1) = tm.reverse(0.0, x, y);
+        assert!(y > UTM_K0 * 10001965.729312723);
+        assert_relative_eq!(lat, 50.0, epsilon = 1e-12);
+        assert_relative_eq!(lon, 120.0, epsilon = 1e-12);
+        assert_relative_eq!(gamma1, gamma, epsilon = 1e-12);
+        assert_relative_eq!(k1, k, epsilon = 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "flattening 0 is not positive")]
+    fn test_sphere() {
+        TransverseMercatorExact::new(WGS84_A, 0.0, UTM_K0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not positive")]
+    fn test_prolate() {
+        TransverseMercatorExact::new(WGS84_A, -1.0 / 150.0, UTM_K0);
+    }
+
+    #[test]
+    #[should_panic(expected = "scale 0 is not positive")]
+    fn test_zero_scale() {
+        TransverseMercatorExact::new(WGS84_A, WGS84_F, 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "equatorial radius")]
+    fn test_infinite_radius() {
+        TransverseMercatorExact::new(f64::INFINITY, WGS84_F, UTM_K0);
+    }
+}
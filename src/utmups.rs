@@ -0,0 +1,448 @@
+//! Conversions between geographic coordinates and the Universal Transverse
+//! Mercator (UTM) and Universal Polar Stereographic (UPS) systems.
+//!
+//! UTM divides the earth between latitudes 80&deg;S and 84&deg;N into 60
+//! zones, numbered 1 through 60, each 6&deg; wide. The zones are projected
+//! with [`TransverseMercator::utm`]. The polar regions are covered by UPS,
+//! which is given the zone number 0 and projected with
+//! [`PolarStereographic::ups`]. The standard zone boundaries are
+//! extended to include the Norway and Svalbard exceptions. The coordinates
+//! include the false eastings and northings.
+//!
+//! Points may be projected into a neighbouring UTM zone, or into UTM beyond
+//! the polar limits, with [`forward_with_zone`]. Projected coordinates are
+//! accepted up to 100 km beyond the nominal limits of each zone.
+//!
+//! # Example
+//! ```rust
+//! use geographiclib_rs::utmups;
+//!
+//! // The Eiffel Tower
+//! let (zone, northp, x, y, _gamma, _k) = utmups::forward(48.8584, 2.2945).unwrap();
+//! assert_eq!((zone, northp), (31, true));
+//! assert_eq!(utmups::encode_zone(zone, northp, true).unwrap(), "31n");
+//!
+//! use approx::assert_relative_eq;
+//! assert_relative_eq!(x, 448252.00, epsilon = 0.01);
+//! assert_relative_eq!(y, 5411954.91, epsilon = 0.01);
+//!
+//! let (lat, lon, _gamma, _k) = utmups::reverse(zone, northp, x, y).unwrap();
+//! assert_relative_eq!(lat, 48.8584, epsilon = 1e-12);
+//! assert_relative_eq!(lon, 2.2945, epsilon = 1e-12);
+//! ```
+
+use std::fmt;
+
+use crate::geomath;
+use crate::PolarStereographic;
+use crate::TransverseMercator;
+
+/// The zone number of UPS.
+pub const UPS: i32 = 0;
+/// The smallest UTM zone number.
+pub const MIN_UTM_ZONE: i32 = 1;
+/// The largest UTM zone number.
+pub const MAX_UTM_ZONE: i32 = 60;
+
+// The size of an MGRS tile (meters). The limits of the UTM and UPS
+// coordinates are all multiples of this.
+pub(in crate) const TILE: f64 = 100e3;
+
+// The false eastings and northings, and the limits of the coordinates,
+// indexed by (utm ? 2 : 0) + (northp ? 1 : 0)
+const FALSE_EASTING: [f64; 4] = [20.0 * TILE, 20.0 * TILE, 5.0 * TILE, 5.0 * TILE];
+const FALSE_NORTHING: [f64; 4] = [20.0 * TILE, 20.0 * TILE, 100.0 * TILE, 0.0];
+const MIN_EASTING: [f64; 4] = [8.0 * TILE, 13.0 * TILE, 1.0 * TILE, 1.0 * TILE];
+const MAX_EASTING: [f64; 4] = [32.0 * TILE, 27.0 * TILE, 9.0 * TILE, 9.0 * TILE];
+const MIN_NORTHING: [f64; 4] = [8.0 * TILE, 13.0 * TILE, 10.0 * TILE, -90.0 * TILE];
+const MAX_NORTHING: [f64; 4] = [32.0 * TILE, 27.0 * TILE, 195.0 * TILE, 95.0 * TILE];
+
+/// The zone to use in [`forward_with_zone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoneSpec {
+    /// The standard zone, UPS poleward of 84&deg;N and 80&deg;S and
+    /// otherwise the UTM zone, including the Norway and Svalbard exceptions.
+    Standard,
+    /// The standard UTM zone, even in the polar regions.
+    Utm,
+    /// A specific zone, 0 for UPS or 1 through 60 for UTM.
+    Zone(i32),
+}
+
+/// The errors from UTM and UPS conversions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UtmUpsError {
+    /// The latitude is not in [-90&deg;, 90&deg;], or a coordinate is NaN.
+    InvalidPosition { lat: f64, lon: f64 },
+    /// The zone is not in [0, 60].
+    InvalidZone(i32),
+    /// The zone string could not be parsed.
+    InvalidZoneString(String),
+    /// The point is too far from the requested zone.
+    OutOfZone { lat: f64, lon: f64, zone: i32 },
+    /// The easting is outside the range for the zone and hemisphere.
+    EastingOutOfRange { x: f64, min: f64, max: f64 },
+    /// The northing is outside the range for the zone and hemisphere.
+    NorthingOutOfRange { y: f64, min: f64, max: f64 },
+}
+
+impl fmt::Display for UtmUpsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UtmUpsError::InvalidPosition { lat, lon } => write!(f, "invalid position {lat}d, {lon}d"),
+            UtmUpsError::InvalidZone(zone) => write!(f, "zone {zone} not in range [0, 60]"),
+            UtmUpsError::InvalidZoneString(s) => write!(f, "illegal zone specification {s:?}"),
+            UtmUpsError::OutOfZone { lat, lon, zone } => {
+                if *zone == UPS {
+                    write!(f, "latitude {lat}d, longitude {lon}d out of legal range for UPS")
+                } else {
+                    write!(f, "latitude {lat}d, longitude {lon}d out of legal range for UTM zone {zone}")
+                }
+            }
+            UtmUpsError::EastingOutOfRange { x, min, max } => {
+                write!(f, "easting {}km not in range [{}km, {}km]", x / 1e3, min / 1e3, max / 1e3)
+            }
+            UtmUpsError::NorthingOutOfRange { y, min, max } => {
+                write!(f, "northing {}km not in range [{}km, {}km]", y / 1e3, min / 1e3, max / 1e3)
+            }
+        }
+    }
+}
+
+impl std::error::Error for UtmUpsError {}
+
+/// The zone for (`lat`, `lon`) selected by `setzone`, 0 for UPS or 1
+/// through 60 for UTM.
+pub fn standard_zone(lat: f64, lon: f64, setzone: ZoneSpec) -> Result<i32, UtmUpsError> {
+    match setzone {
+        ZoneSpec::Zone(zone) if (UPS..=MAX_UTM_ZONE).contains(&zone) => return Ok(zone),
+        ZoneSpec::Zone(zone) => return Err(UtmUpsError::InvalidZone(zone)),
+        _ => {}
+    }
+    if lat.is_nan() || lon.is_nan() {
+        return Err(UtmUpsError::InvalidPosition { lat, lon });
+    }
+    if setzone == ZoneSpec::Utm || (lat >= -80.0 && lat < 84.0) {
+        let mut ilon = geomath::ang_normalize(lon).floor() as i32;
+        // ilon now in [-180, 180)
+        if ilon == 180 {
+            ilon = -180;
+        }
+        let zone = (ilon + 186) / 6;
+        let band = latitude_band(lat);
+        if band == 7 && zone == 31 && ilon >= 3 {
+            // The Norway exception
+            Ok(32)
+        } else if band == 9 && ilon >= 0 && ilon < 42 {
+            // The Svalbard exception
+            Ok(2 * ((ilon + 183) / 12) + 1)
+        } else {
+            Ok(zone)
+        }
+    } else {
+        Ok(UPS)
+    }
+}
+
+/// The central meridian (degrees) of a UTM zone.
+pub fn central_meridian(zone: i32) -> f64 {
+    (6 * zone - 183) as f64
+}
+
+/// Convert (`lat`, `lon`) to UTM or UPS in the standard zone.
+///
+/// # Returns
+/// - zone 0 for UPS or 1 through 60 for UTM.
+/// - northp true for the northern hemisphere.
+/// - x easting of point (meters).
+/// - y northing of point (meters).
+/// - gamma meridian convergence at point (degrees).
+/// - k scale of projection at point.
+pub fn forward(lat: f64, lon: f64) -> Result<(i32, bool, f64, f64, f64, f64), UtmUpsError> {
+    forward_with_zone(lat, lon, ZoneSpec::Standard)
+}
+
+/// Convert (`lat`, `lon`) to UTM or UPS in the zone selected by `setzone`,
+/// see [`forward`].
+///
+/// UTM zones may be used up to 60&deg; from their central meridians, and UPS
+/// up to 20&deg; from the poles, as long as the coordinates lie within 100 km
+/// of the limits of the zone.
+pub fn forward_with_zone(
+    lat: f64,
+    lon: f64,
+    setzone: ZoneSpec,
+) -> Result<(i32, bool, f64, f64, f64, f64), UtmUpsError> {
+    forward_checked(lat, lon, setzone, false)
+}
+
+pub(in crate) fn forward_checked(
+    lat: f64,
+    lon: f64,
+    setzone: ZoneSpec,
+    mgrslimits: bool,
+) -> Result<(i32, bool, f64, f64, f64, f64), UtmUpsError> {
+    if lat.abs() > 90.0 {
+        return Err(UtmUpsError::InvalidPosition { lat, lon });
+    }
+    let northp = !lat.is_sign_negative();
+    let zone = standard_zone(lat, lon, setzone)?;
+    let utmp = zone != UPS;
+    let (x, y, gamma, k) = if utmp {
+        let lon0 = central_meridian(zone);
+        let (dlon, _) = geomath::ang_diff(lon0, lon);
+        if dlon.abs() > 60.0 {
+            return Err(UtmUpsError::OutOfZone { lat, lon, zone });
+        }
+        TransverseMercator::utm().forward(lon0, lat, lon)
+    } else {
+        if lat.abs() < 70.0 {
+            return Err(UtmUpsError::OutOfZone { lat, lon, zone });
+        }
+        PolarStereographic::ups().forward(northp, lat, lon)
+    };
+    let ind = index(utmp, northp);
+    let x = x + FALSE_EASTING[ind];
+    let y = y + FALSE_NORTHING[ind];
+    if check_coords(utmp, northp, x, y, mgrslimits).is_err() {
+        return Err(UtmUpsError::OutOfZone { lat, lon, zone });
+    }
+    Ok((zone, northp, x, y, gamma, k))
+}
+
+/// Convert UTM or UPS coordinates to geographic coordinates.
+///
+/// `zone` is 0 for UPS or 1 through 60 for UTM, and `northp` selects the
+/// hemisphere.
+///
+/// # Returns
+/// - lat latitude of point (degrees).
+/// - lon longitude of point (degrees).
+/// - gamma meridian convergence at point (degrees).
+/// - k scale of projection at point.
+pub fn reverse(zone: i32, northp: bool, x: f64, y: f64) -> Result<(f64, f64, f64, f64), UtmUpsError> {
+    reverse_checked(zone, northp, x, y, false)
+}
+
+pub(in crate) fn reverse_checked(
+    zone: i32,
+    northp: bool,
+    x: f64,
+    y: f64,
+    mgrslimits: bool,
+) -> Result<(f64, f64, f64, f64), UtmUpsError> {
+    if !(UPS..=MAX_UTM_ZONE).contains(&zone) {
+        return Err(UtmUpsError::InvalidZone(zone));
+    }
+    let utmp = zone != UPS;
+    check_coords(utmp, northp, x, y, mgrslimits)?;
+    let ind = index(utmp, northp);
+    let x = x - FALSE_EASTING[ind];
+    let y = y - FALSE_NORTHING[ind];
+    if utmp {
+        Ok(TransverseMercator::utm().reverse(central_meridian(zone), x, y))
+    } else {
+        Ok(PolarStereographic::ups().reverse(northp, x, y))
+    }
+}
+
+// Check that the easting and northing are within the limits for the zone,
+// extended by 100 km unless mgrslimits is set. NaNs pass.
+pub(in crate) fn check_coords(utmp: bool, northp: bool, x: f64, y: f64, mgrslimits: bool) -> Result<(), UtmUpsError> {
+    let slop = if mgrslimits { 0.0 } else { TILE };
+    let ind = index(utmp, northp);
+    let (min, max) = (MIN_EASTING[ind] - slop, MAX_EASTING[ind] + slop);
+    if x < min || x > max {
+        return Err(UtmUpsError::EastingOutOfRange { x, min, max });
+    }
+    let (min, max) = (MIN_NORTHING[ind] - slop, MAX_NORTHING[ind] + slop);
+    if y < min || y > max {
+        return Err(UtmUpsError::NorthingOutOfRange { y, min, max });
+    }
+    Ok(())
+}
+
+fn index(utmp: bool, northp: bool) -> usize {
+    (if utmp { 2 } else { 0 }) + (if northp { 1 } else { 0 })
+}
+
+// The MGRS latitude band, -10 (C) through 9 (X), with band X extended to
+// 84N and bands below 80S clamped to band C
+pub(in crate) fn latitude_band(lat: f64) -> i32 {
+    let ilat = lat.floor() as i32;
+    ((ilat + 80) / 8 - 10).clamp(-10, 9)
+}
+
+/// The zone and hemisphere as a string, e.g. "38n" or "38north" for UTM,
+/// and "n" or "north" for UPS.
+pub fn encode_zone(zone: i32, northp: bool, abbrev: bool) -> Result<String, UtmUpsError> {
+    if !(UPS..=MAX_UTM_ZONE).contains(&zone) {
+        return Err(UtmUpsError::InvalidZone(zone));
+    }
+    let hemi = match (northp, abbrev) {
+        (true, true) => "n",
+        (true, false) => "north",
+        (false, true) => "s",
+        (false, false) => "south",
+    };
+    if zone == UPS {
+        Ok(hemi.to_string())
+    } else {
+        Ok(format!("{zone:02}{hemi}"))
+    }
+}
+
+/// Parse a zone and hemisphere string, the inverse of [`encode_zone`],
+/// returning (zone, northp). The hemisphere is case insensitive.
+pub fn decode_zone(zonestr: &str) -> Result<(i32, bool), UtmUpsError> {
+    let err = || UtmUpsError::InvalidZoneString(zonestr.to_string());
+    let ndigits = zonestr.bytes().take_while(u8::is_ascii_digit).count();
+    let zone = if ndigits == 0 {
+        UPS
+    } else if ndigits > 2 {
+        return Err(err());
+    } else {
+        let zone = zonestr[..ndigits].parse().map_err(|_| err())?;
+        // Don't allow 0n as an alternative to n for UPS coordinates
+        if !(MIN_UTM_ZONE..=MAX_UTM_ZONE).contains(&zone) {
+            return Err(UtmUpsError::InvalidZone(zone));
+        }
+        zone
+    };
+    let hemi = zonestr[ndigits..].to_ascii_lowercase();
+    match hemi.as_str() {
+        "n" | "north" => Ok((zone, true)),
+        "s" | "south" => Ok((zone, false)),
+        _ => Err(err()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_standard_zone() {
+        let zone = |lat, lon| standard_zone(lat, lon, ZoneSpec::Standard).unwrap();
+        assert_eq!(zone(0.0, -180.0), 1);
+        assert_eq!(zone(0.0, 180.0), 1);
+        assert_eq!(zone(0.0, 179.9), 60);
+        assert_eq!(zone(0.0, 0.0), 31);
+        assert_eq!(zone(-0.5, -0.5), 30);
+        // The Norway exception
+        assert_eq!(zone(60.0, 2.9), 31);
+        assert_eq!(zone(60.0, 3.0), 32);
+        assert_eq!(zone(55.9, 3.0), 31);
+        assert_eq!(zone(64.0, 3.0), 31);
+        // The Svalbard exception
+        assert_eq!(zone(75.0, 8.9), 31);
+        assert_eq!(zone(75.0, 9.0), 33);
+        assert_eq!(zone(75.0, 20.9), 33);
+        assert_eq!(zone(75.0, 21.0), 35);
+        assert_eq!(zone(75.0, 33.0), 37);
+        assert_eq!(zone(75.0, 42.0), 38);
+        assert_eq!(zone(71.9, 9.0), 32);
+        // The polar limits
+        assert_eq!(zone(83.9, 0.0), 31);
+        assert_eq!(zone(84.0, 0.0), UPS);
+        assert_eq!(zone(-80.0, 0.0), 31);
+        assert_eq!(zone(-80.1, 0.0), UPS);
+
+        assert_eq!(standard_zone(85.0, 0.0, ZoneSpec::Utm), Ok(31));
+        assert_eq!(standard_zone(85.0, 0.0, ZoneSpec::Zone(30)), Ok(30));
+        assert_eq!(standard_zone(85.0, 0.0, ZoneSpec::Zone(61)), Err(UtmUpsError::InvalidZone(61)));
+        assert!(standard_zone(f64::NAN, 0.0, ZoneSpec::Standard).is_err());
+    }
+
+    #[test]
+    fn test_forward_reverse() {
+        for &(lat, lon) in [(48.8584, 2.2945), (-33.8568, 151.2153), (0.0, 0.0), (60.0, 5.0), (78.0, 15.0)].iter() {
+            let (zone, northp, x, y, gamma, k) = forward(lat, lon).unwrap();
+            assert_eq!(zone, standard_zone(lat, lon, ZoneSpec::Standard).unwrap());
+            assert_eq!(northp, lat >= 0.0);
+            let (x0, y0, gamma0, k0) = TransverseMercator::utm().forward(central_meridian(zone), lat, lon);
+            assert_eq!(x, x0 + 500e3);
+            assert_eq!(y, y0 + if northp { 0.0 } else { 10e6 });
+            assert_eq!((gamma, k), (gamma0, k0));
+            let (lat1, lon1, gamma1, k1) = reverse(zone, northp, x, y).unwrap();
+            assert_relative_eq!(lat1, lat, epsilon = 1e-12);
+            assert_relative_eq!(lon1, lon, epsilon = 1e-12);
+            assert_relative_eq!(gamma1, gamma, epsilon = 1e-12);
+            assert_relative_eq!(k1, k, epsilon = 1e-12);
+        }
+
+        // UPS
+        for &(lat, lon) in [(85.0, 30.0), (-85.0, -120.0), (90.0, 0.0), (-90.0, 0.0)].iter() {
+            let (zone, northp, x, y, _, _) = forward(lat, lon).unwrap();
+            assert_eq!(zone, UPS);
+            let (x0, y0, _, _) = PolarStereographic::ups().forward(northp, lat, lon);
+            assert_eq!((x, y), (x0 + 2e6, y0 + 2e6));
+            let (lat1, lon1, _, _) = reverse(zone, northp, x, y).unwrap();
+            assert_relative_eq!(lat1, lat, epsilon = 1e-12);
+            if lat.abs() != 90.0 {
+                assert_relative_eq!(lon1, lon, epsilon = 1e-12);
+            }
+        }
+        let (_, _, x, y, _, k) = forward(90.0, 0.0).unwrap();
+        assert_eq!((x, y, k), (2e6, 2e6, 0.994));
+    }
+
+    #[test]
+    fn test_zone_overrides() {
+        // Project into the neighbouring zone
+        let (zone, northp, x, y, _, _) = forward_with_zone(45.0, 2.9, ZoneSpec::Zone(30)).unwrap();
+        assert_eq!((zone, northp), (30, true));
+        assert!(x > 900e3);
+        let (lat, lon, _, _) = reverse(zone, northp, x, y).unwrap();
+        assert_relative_eq!(lat, 45.0, epsilon = 1e-12);
+        assert_relative_eq!(lon, 2.9, epsilon = 1e-12);
+
+        // UTM in the polar regions and UPS at lower latitudes
+        let (zone, _, _, _, _, _) = forward_with_zone(85.0, 0.0, ZoneSpec::Utm).unwrap();
+        assert_eq!(zone, 31);
+        let (zone, _, _, _, _, _) = forward_with_zone(83.0, 0.0, ZoneSpec::Zone(UPS)).unwrap();
+        assert_eq!(zone, UPS);
+
+        // But not too far from the zone
+        assert_eq!(
+            forward_with_zone(45.0, 2.9, ZoneSpec::Zone(20)),
+            Err(UtmUpsError::OutOfZone { lat: 45.0, lon: 2.9, zone: 20 })
+        );
+        assert!(forward_with_zone(0.0, 20.0, ZoneSpec::Zone(31)).is_err());
+        assert!(forward_with_zone(60.0, 0.0, ZoneSpec::Zone(UPS)).is_err());
+        assert_eq!(forward(91.0, 0.0), Err(UtmUpsError::InvalidPosition { lat: 91.0, lon: 0.0 }));
+    }
+
+    #[test]
+    fn test_reverse_limits() {
+        assert!(reverse(31, true, 500e3, 9600e3).is_ok());
+        assert!(reverse(31, true, -1.0, 0.0).is_err());
+        assert!(reverse(31, false, 500e3, 19700e3).is_err());
+        assert!(reverse(UPS, true, 2e6, 3.5e6).is_err());
+        assert_eq!(reverse(61, true, 500e3, 0.0), Err(UtmUpsError::InvalidZone(61)));
+        // The MGRS limits have no slop
+        assert!(reverse_checked(31, true, 50e3, 0.0, false).is_ok());
+        assert!(reverse_checked(31, true, 50e3, 0.0, true).is_err());
+    }
+
+    #[test]
+    fn test_encode_decode_zone() {
+        assert_eq!(encode_zone(38, true, true).unwrap(), "38n");
+        assert_eq!(encode_zone(5, false, false).unwrap(), "05south");
+        assert_eq!(encode_zone(UPS, true, false).unwrap(), "north");
+        assert!(encode_zone(61, true, true).is_err());
+
+        assert_eq!(decode_zone("38n"), Ok((38, true)));
+        assert_eq!(decode_zone("5S"), Ok((5, false)));
+        assert_eq!(decode_zone("05south"), Ok((5, false)));
+        assert_eq!(decode_zone("North"), Ok((UPS, true)));
+        assert_eq!(decode_zone("s"), Ok((UPS, false)));
+        assert!(decode_zone("0n").is_err());
+        assert!(decode_zone("61n").is_err());
+        assert!(decode_zone("038n").is_err());
+        assert!(decode_zone("38").is_err());
+        assert!(decode_zone("38x").is_err());
+        assert!(decode_zone("").is_err());
+    }
+}